@@ -0,0 +1,66 @@
+//! Compares dispatch through the `TypeId`-keyed `EventManager` with a `define_events!` manager.
+//! Run with `cargo bench --bench closed`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use event_forge::{define_events, EventManager};
+
+const ROUNDS: u32 = 200_000;
+
+struct A(u64);
+struct B(u64);
+struct C(u64);
+struct D(u64);
+
+define_events!(Closed { A, B, C, D });
+
+// The same listeners on either manager; both have `subscribe` with this shape.
+macro_rules! populate {
+    ($event_manager:expr) => {
+        $event_manager.subscribe(|event: &A| {
+            black_box(event.0);
+        });
+        $event_manager.subscribe(|event: &B| {
+            black_box(event.0);
+        });
+        $event_manager.subscribe(|event: &C| {
+            black_box(event.0);
+        });
+        $event_manager.subscribe(|event: &D| {
+            black_box(event.0);
+        });
+    };
+}
+
+macro_rules! run {
+    ($event_manager:expr) => {{
+        let started = Instant::now();
+        for i in 0..u64::from(ROUNDS) {
+            $event_manager.dispatch(&A(i));
+            $event_manager.dispatch(&B(i));
+            $event_manager.dispatch(&C(i));
+            $event_manager.dispatch(&D(i));
+        }
+        started.elapsed()
+    }};
+}
+
+fn report(name: &str, elapsed: Duration) {
+    let per_dispatch = elapsed.as_nanos() as f64 / f64::from(ROUNDS * 4);
+    println!("{name:>12}: {per_dispatch:6.1} ns/dispatch");
+}
+
+fn main() {
+    let mut keyed = EventManager::new();
+    populate!(keyed);
+    let mut closed = Closed::new();
+    populate!(closed);
+
+    // Warm up both before measuring.
+    let _ = run!(keyed);
+    let _ = run!(closed);
+
+    report("TypeId map", run!(keyed));
+    report("closed", run!(closed));
+}
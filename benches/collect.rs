@@ -0,0 +1,69 @@
+//! Compares allocations and time of `collect`, which returns a fresh `Vec` per call, with
+//! `dispatch_into` reusing one buffer. Run with `cargo bench --bench collect`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use event_forge::EventManager;
+
+// Counts every allocation made by the process.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const ROUNDS: u64 = 200_000;
+const EMITTERS: u64 = 8;
+
+struct Frame(u64);
+
+fn measure(name: &str, mut run: impl FnMut()) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let started = Instant::now();
+    run();
+    report(name, started.elapsed(), ALLOCATIONS.load(Ordering::Relaxed) - allocations);
+}
+
+fn report(name: &str, elapsed: Duration, allocations: usize) {
+    let per_call = elapsed.as_nanos() as f64 / ROUNDS as f64;
+    let allocations_per_call = allocations as f64 / ROUNDS as f64;
+    println!("{name:>14}: {per_call:6.1} ns/call, {allocations_per_call:4.2} allocations/call ({EMITTERS} emitters)");
+}
+
+fn main() {
+    let mut event_manager = EventManager::new();
+    for i in 0..EMITTERS {
+        event_manager.subscribe_emitter(move |event: &Frame, out: &mut Vec<u64>| out.push(event.0 + i));
+    }
+
+    measure("collect", || {
+        for i in 0..ROUNDS {
+            let out: Vec<u64> = event_manager.collect(&Frame(i));
+            black_box(out);
+        }
+    });
+
+    let mut out: Vec<u64> = Vec::new();
+    measure("dispatch_into", || {
+        for i in 0..ROUNDS {
+            out.clear();
+            event_manager.dispatch_into(&Frame(i), &mut out);
+            black_box(&out);
+        }
+    });
+}
@@ -0,0 +1,179 @@
+//! Compares dispatch through a regular `EventManager` with a frozen one, first with a few
+//! busy types, then with many types of one listener each, where the type lookup dominates.
+//! Run with `cargo bench --bench frozen`.
+
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use event_forge::{EventManager, FrozenEventManager};
+
+const ROUNDS: u32 = 200_000;
+const LISTENERS: u64 = 8;
+
+struct A(u64);
+struct B(u64);
+struct C(u64);
+struct D(u64);
+
+fn populate(event_manager: &mut EventManager) {
+    for _ in 0..LISTENERS {
+        event_manager.subscribe(|event: &A| {
+            black_box(event.0);
+        });
+        event_manager.subscribe(|event: &B| {
+            black_box(event.0);
+        });
+        event_manager.subscribe(|event: &C| {
+            black_box(event.0);
+        });
+        event_manager.subscribe(|event: &D| {
+            black_box(event.0);
+        });
+    }
+}
+
+fn run_mutable(event_manager: &mut EventManager) -> Duration {
+    let started = Instant::now();
+    for i in 0..u64::from(ROUNDS) {
+        event_manager.dispatch(&A(i));
+        event_manager.dispatch(&B(i));
+        event_manager.dispatch(&C(i));
+        event_manager.dispatch(&D(i));
+    }
+    started.elapsed()
+}
+
+fn run_frozen(event_manager: &mut FrozenEventManager) -> Duration {
+    let started = Instant::now();
+    for i in 0..u64::from(ROUNDS) {
+        event_manager.dispatch(&A(i));
+        event_manager.dispatch(&B(i));
+        event_manager.dispatch(&C(i));
+        event_manager.dispatch(&D(i));
+    }
+    started.elapsed()
+}
+
+fn report(name: &str, elapsed: Duration) {
+    let per_dispatch = elapsed.as_nanos() as f64 / f64::from(ROUNDS * 4);
+    println!("{name:>12}: {per_dispatch:6.1} ns/dispatch ({LISTENERS} listeners each)");
+}
+
+// One distinct event type per `N`.
+struct Many<const N: usize>(u64);
+
+const TYPES: u32 = 64;
+
+// Expands `$callback!(N, $args)` for every `N` in `0..TYPES`.
+macro_rules! for_many_types {
+    ($callback:ident, $args:tt) => {
+        seq!($callback, $args; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31
+            32 33 34 35 36 37 38 39 40 41 42 43 44 45 46 47 48 49 50 51 52 53 54 55 56 57 58 59 60 61 62 63)
+    };
+}
+
+macro_rules! seq {
+    ($callback:ident, $args:tt; $($n:literal)*) => {
+        $($callback!($n, $args);)*
+    };
+}
+
+macro_rules! subscribe_many {
+    ($n:literal, ($event_manager:expr)) => {
+        $event_manager.subscribe(|event: &Many<$n>| {
+            black_box(event.0);
+        });
+    };
+}
+
+macro_rules! dispatch_many {
+    ($n:literal, ($event_manager:expr, $i:expr)) => {
+        $event_manager.dispatch(&Many::<$n>($i));
+    };
+}
+
+macro_rules! type_id_many {
+    ($n:literal, ($ids:expr)) => {
+        $ids.push(TypeId::of::<Many<$n>>());
+    };
+}
+
+fn run_many_mutable(event_manager: &mut EventManager) -> Duration {
+    let started = Instant::now();
+    for i in 0..u64::from(ROUNDS / 16) {
+        for_many_types!(dispatch_many, (event_manager, i));
+    }
+    started.elapsed()
+}
+
+fn run_many_frozen(event_manager: &mut FrozenEventManager) -> Duration {
+    let started = Instant::now();
+    for i in 0..u64::from(ROUNDS / 16) {
+        for_many_types!(dispatch_many, (event_manager, i));
+    }
+    started.elapsed()
+}
+
+// The type lookup alone: a `HashMap`, as both managers use, against binary search in a
+// sorted table, which measured slower here, so the frozen manager keeps its `HashMap`.
+fn run_lookups(ids: &[TypeId]) -> (Duration, Duration) {
+    let map: HashMap<TypeId, usize> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+    let mut sorted: Vec<(TypeId, usize)> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+    sorted.sort_unstable_by_key(|(id, _)| *id);
+
+    let started = Instant::now();
+    for _ in 0..ROUNDS / 16 {
+        for id in ids {
+            black_box(map.get(black_box(id)));
+        }
+    }
+    let hashed = started.elapsed();
+
+    let started = Instant::now();
+    for _ in 0..ROUNDS / 16 {
+        for id in ids {
+            black_box(sorted.binary_search_by_key(black_box(id), |(listened, _)| *listened).ok());
+        }
+    }
+    (hashed, started.elapsed())
+}
+
+fn report_many(name: &str, elapsed: Duration) {
+    let per_lookup = elapsed.as_nanos() as f64 / f64::from(ROUNDS / 16 * TYPES);
+    println!("{name:>12}: {per_lookup:6.1} ns/dispatch ({TYPES} types, 1 listener each)");
+}
+
+fn main() {
+    let mut mutable = EventManager::new();
+    populate(&mut mutable);
+
+    let mut frozen = EventManager::new();
+    populate(&mut frozen);
+    let mut frozen = frozen.freeze();
+
+    // Warm up both before measuring.
+    run_mutable(&mut mutable);
+    run_frozen(&mut frozen);
+
+    report("mutable", run_mutable(&mut mutable));
+    report("frozen", run_frozen(&mut frozen));
+
+    let mut mutable = EventManager::new();
+    for_many_types!(subscribe_many, (mutable));
+    let mut frozen = EventManager::new();
+    for_many_types!(subscribe_many, (frozen));
+    let mut frozen = frozen.freeze();
+    run_many_mutable(&mut mutable);
+    run_many_frozen(&mut frozen);
+    report_many("mutable", run_many_mutable(&mut mutable));
+    report_many("frozen", run_many_frozen(&mut frozen));
+
+    let mut ids = Vec::with_capacity(TYPES as usize);
+    for_many_types!(type_id_many, (ids));
+    run_lookups(&ids);
+    let (hashed, sorted) = run_lookups(&ids);
+    report_many("HashMap get", hashed);
+    report_many("binary search", sorted);
+}
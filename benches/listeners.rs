@@ -0,0 +1,36 @@
+//! Measures per-listener dispatch cost for a single event type with many listeners.
+//! Run with `cargo bench --bench listeners`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use event_forge::EventManager;
+
+const ROUNDS: u32 = 2_000;
+const LISTENERS: u32 = 1_000;
+
+struct Moved(u64);
+
+fn run(event_manager: &mut EventManager) -> Duration {
+    let started = Instant::now();
+    for i in 0..u64::from(ROUNDS) {
+        event_manager.dispatch(&Moved(i));
+    }
+    started.elapsed()
+}
+
+fn main() {
+    let mut event_manager = EventManager::new();
+    for _ in 0..LISTENERS {
+        event_manager.subscribe(|event: &Moved| {
+            black_box(event.0);
+        });
+    }
+
+    // Warm up before measuring.
+    run(&mut event_manager);
+
+    let elapsed = run(&mut event_manager);
+    let per_listener = elapsed.as_nanos() as f64 / f64::from(ROUNDS * LISTENERS);
+    println!("{LISTENERS} listeners: {per_listener:5.2} ns/listener");
+}
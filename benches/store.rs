@@ -0,0 +1,88 @@
+//! Compares dispatch through the default `RandomState`-hashed store with an Fx-style hasher.
+//! Run with `cargo bench --bench store`.
+
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use event_forge::{EventManager, ListenerBucket};
+
+const ROUNDS: u32 = 200_000;
+
+// The rustc-hash "Fx" algorithm; `TypeId`s are already well mixed, so this is plenty.
+#[derive(Default)]
+struct FxHasher {
+    hash: u64,
+}
+
+impl Hasher for FxHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0u8; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.write_u64(u64::from_le_bytes(word));
+        }
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ value).wrapping_mul(0x51_7c_c1_b7_27_22_0a_95);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+struct A(u64);
+struct B(u64);
+struct C(u64);
+struct D(u64);
+
+fn populate(event_manager: &mut EventManager) {
+    event_manager.subscribe(|event: &A| {
+        black_box(event.0);
+    });
+    event_manager.subscribe(|event: &B| {
+        black_box(event.0);
+    });
+    event_manager.subscribe(|event: &C| {
+        black_box(event.0);
+    });
+    event_manager.subscribe(|event: &D| {
+        black_box(event.0);
+    });
+}
+
+fn run(event_manager: &mut EventManager) -> Duration {
+    let started = Instant::now();
+    for i in 0..u64::from(ROUNDS) {
+        event_manager.dispatch(&A(i));
+        event_manager.dispatch(&B(i));
+        event_manager.dispatch(&C(i));
+        event_manager.dispatch(&D(i));
+    }
+    started.elapsed()
+}
+
+fn report(name: &str, elapsed: Duration) {
+    let per_dispatch = elapsed.as_nanos() as f64 / f64::from(ROUNDS * 4);
+    println!("{name:>12}: {per_dispatch:6.1} ns/dispatch");
+}
+
+fn main() {
+    let mut default_store = EventManager::new();
+    populate(&mut default_store);
+
+    let fx_map: HashMap<TypeId, ListenerBucket, BuildHasherDefault<FxHasher>> = HashMap::default();
+    let mut fx_store = EventManager::with_store(fx_map);
+    populate(&mut fx_store);
+
+    // Warm up both before measuring.
+    run(&mut default_store);
+    run(&mut fx_store);
+
+    report("RandomState", run(&mut default_store));
+    report("Fx", run(&mut fx_store));
+}
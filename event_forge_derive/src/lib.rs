@@ -0,0 +1,141 @@
+//! `#[derive(Event)]` for `event_forge::Event`, re-exported by `event_forge` itself.
+//!
+//! Written against `proc_macro` alone so the crate has no dependencies; it only needs to
+//! find the type's name and the `#[event(..)]` attribute.
+
+use proc_macro::{Delimiter, TokenStream, TokenTree};
+
+/// Implements `event_forge::Event` for a struct or enum without generic parameters.
+///
+/// By default the event's `NAME` is the type's name and it has no category. Both can be
+/// set with `#[event(name = "player.jumped", category = "gameplay")]`; with `#[event(debug)]`
+/// `describe` uses the type's `Debug` output instead of its name. `#[event(priority = 10)]`
+/// sets the `PRIORITY` that `subscribe_event` listeners get by default.
+#[proc_macro_derive(Event, attributes(event))]
+pub fn derive_event(input: TokenStream) -> TokenStream {
+    match expand(input) {
+        Ok(output) => output.parse().expect("the generated impl is valid Rust"),
+        Err(message) => format!("::core::compile_error!({message:?});").parse().expect("compile_error! is valid Rust"),
+    }
+}
+
+#[derive(Default)]
+struct EventAttributes {
+    // String literals, kept as written so escapes survive.
+    name: Option<String>,
+    category: Option<String>,
+    priority: Option<i32>,
+    debug: bool,
+}
+
+fn expand(input: TokenStream) -> Result<String, String> {
+    let mut attributes = EventAttributes::default();
+    let mut tokens = input.into_iter().peekable();
+    let type_name = loop {
+        match tokens.next() {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == '#' => {
+                if let Some(TokenTree::Group(group)) = tokens.next() {
+                    parse_attribute(group.stream(), &mut attributes)?;
+                }
+            }
+            Some(TokenTree::Ident(ident)) if matches!(ident.to_string().as_str(), "struct" | "enum" | "union") => {
+                match tokens.next() {
+                    Some(TokenTree::Ident(name)) => break name.to_string(),
+                    _ => return Err("expected a type name".to_string()),
+                }
+            }
+            Some(_) => {}
+            None => return Err("#[derive(Event)] only applies to structs, enums and unions".to_string()),
+        }
+    };
+    if matches!(tokens.peek(), Some(TokenTree::Punct(punct)) if punct.as_char() == '<') {
+        return Err(format!("#[derive(Event)] doesn't support generic types; implement `Event` for `{type_name}` by hand"));
+    }
+
+    let name = attributes.name.unwrap_or_else(|| format!("{type_name:?}"));
+    let category = attributes.category.map_or("::core::option::Option::None".to_string(), |category| {
+        format!("::core::option::Option::Some({category})")
+    });
+    let priority = attributes.priority.map_or(String::new(), |priority| format!("const PRIORITY: i32 = {priority};"));
+    let describe = if attributes.debug {
+        "fn describe(&self) -> ::std::string::String { ::std::format!(\"{:?}\", self) }"
+    } else {
+        ""
+    };
+    Ok(format!(
+        "impl ::event_forge::Event for {type_name} {{
+            const NAME: &'static str = {name};
+            const CATEGORY: ::core::option::Option<&'static str> = {category};
+            {priority}
+            {describe}
+        }}"
+    ))
+}
+
+// Reads `event(name = "..", category = "..", priority = N, debug)`; other attributes are ignored.
+fn parse_attribute(attribute: TokenStream, attributes: &mut EventAttributes) -> Result<(), String> {
+    let mut tokens = attribute.into_iter();
+    match tokens.next() {
+        Some(TokenTree::Ident(ident)) if ident.to_string() == "event" => {}
+        _ => return Ok(()),
+    }
+    let Some(TokenTree::Group(arguments)) = tokens.next() else {
+        return Err("expected #[event(..)]".to_string());
+    };
+    if arguments.delimiter() != Delimiter::Parenthesis {
+        return Err("expected #[event(..)]".to_string());
+    }
+
+    let mut arguments = arguments.stream().into_iter();
+    while let Some(token) = arguments.next() {
+        let key = match token {
+            TokenTree::Ident(ident) => ident.to_string(),
+            TokenTree::Punct(punct) if punct.as_char() == ',' => continue,
+            other => return Err(format!("unexpected `{other}` in #[event(..)]")),
+        };
+        if key == "debug" {
+            attributes.debug = true;
+            continue;
+        }
+        if !matches!(key.as_str(), "name" | "category" | "priority") {
+            return Err(format!("unknown #[event(..)] key `{key}`; expected name, category, priority or debug"));
+        }
+        if !matches!(arguments.next(), Some(TokenTree::Punct(eq)) if eq.as_char() == '=') {
+            return Err(format!("expected `{key} = ..` in #[event(..)]"));
+        }
+        match key.as_str() {
+            "name" => attributes.name = Some(string_value(&key, arguments.next())?),
+            "category" => attributes.category = Some(string_value(&key, arguments.next())?),
+            _ => attributes.priority = Some(priority_value(&mut arguments)?),
+        }
+    }
+    Ok(())
+}
+
+// A string literal value, kept as written.
+fn string_value(key: &str, token: Option<TokenTree>) -> Result<String, String> {
+    match token {
+        Some(TokenTree::Literal(value)) if is_string(&value.to_string()) => Ok(value.to_string()),
+        _ => Err(format!("expected `{key} = \"..\"` in #[event(..)]")),
+    }
+}
+
+// An `i32` literal value, optionally negative.
+fn priority_value(arguments: &mut impl Iterator<Item = TokenTree>) -> Result<i32, String> {
+    let mut token = arguments.next();
+    let negative = matches!(&token, Some(TokenTree::Punct(minus)) if minus.as_char() == '-');
+    if negative {
+        token = arguments.next();
+    }
+    let digits = match token {
+        Some(TokenTree::Literal(value)) => value.to_string(),
+        _ => return Err("expected `priority = <integer>` in #[event(..)]".to_string()),
+    };
+    let digits = digits.strip_suffix("i32").unwrap_or(&digits).replace('_', "");
+    let value: i64 = digits.parse().map_err(|_| format!("`{digits}` is not a valid priority; expected an i32"))?;
+    i32::try_from(if negative { -value } else { value }).map_err(|_| format!("priority `{digits}` doesn't fit in an i32"))
+}
+
+fn is_string(literal: &str) -> bool {
+    literal.starts_with('"') || literal.starts_with("r\"") || literal.starts_with("r#")
+}
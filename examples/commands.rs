@@ -0,0 +1,63 @@
+//! Dispatches `Box<dyn Command>` events through a `TraitEventManager`: listeners subscribe
+//! to the `Command` trait as a whole rather than to each concrete command type.
+//! Run with `cargo run --example commands`.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use event_forge::TraitEventManager;
+
+trait Command {
+    fn name(&self) -> &str;
+    fn apply(&self, position: &mut (i32, i32));
+}
+
+struct Move {
+    dx: i32,
+    dy: i32,
+}
+
+struct Teleport {
+    to: (i32, i32),
+}
+
+impl Command for Move {
+    fn name(&self) -> &str {
+        "move"
+    }
+
+    fn apply(&self, position: &mut (i32, i32)) {
+        position.0 += self.dx;
+        position.1 += self.dy;
+    }
+}
+
+impl Command for Teleport {
+    fn name(&self) -> &str {
+        "teleport"
+    }
+
+    fn apply(&self, position: &mut (i32, i32)) {
+        *position = self.to;
+    }
+}
+
+fn main() {
+    let mut commands: TraitEventManager<dyn Command> = TraitEventManager::new();
+    let position = Rc::new(RefCell::new((0, 0)));
+
+    commands.subscribe(|command| println!("executing {}", command.name()));
+    let player = Rc::clone(&position);
+    commands.subscribe(move |command| command.apply(&mut player.borrow_mut()));
+
+    let queued: Vec<Box<dyn Command>> = vec![
+        Box::new(Move { dx: 1, dy: 0 }),
+        Box::new(Teleport { to: (10, 10) }),
+        Box::new(Move { dx: 0, dy: -3 }),
+    ];
+    for command in &queued {
+        commands.dispatch(command.as_ref());
+    }
+
+    println!("final position: {:?}", position.borrow());
+}
@@ -0,0 +1,157 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+use crate::{EventManager, Step, SubscriptionId};
+
+// Builds the `Dst` event handed to the listeners of an aliased type.
+type Converter = Box<dyn Fn(&dyn Any) -> Box<dyn Any>>;
+
+// One `alias_event` registration, keyed by its source type.
+pub(crate) struct Alias {
+    target: TypeId,
+    target_name: &'static str,
+    convert: Converter,
+}
+
+impl EventManager {
+    /// Makes `dispatch` of `Src` also reach the listeners of `Dst`, handing them the event
+    /// `convert` builds from it, e.g. to keep listeners of a renamed event type working
+    /// during a migration. `Dst` listeners run after all `Src` listeners, and before any
+    /// chained follow-ups; a `Src` may alias several types. Aliases don't chain: a `Dst`
+    /// that is itself aliased doesn't forward further. Replaces any earlier `Src` to `Dst`
+    /// alias.
+    ///
+    /// Aliases are followed by `dispatch`, `dispatch_scoped`, `dispatch_with_ack`,
+    /// `dispatch_with_deadline`, `dispatch_dyn`, `dispatch_mixed`, `dispatch_event`, `flush`,
+    /// `flush_sorted`, `flush_until_stable` and `FrozenEventManager::dispatch`, by the
+    /// methods built on them: `pump_from`, `dispatch_latching`, `dispatch_deduped`,
+    /// `dispatch_if_subscribed` and `emit` on an `EventManager`, and by the events chaining
+    /// listeners enqueue, whichever dispatch they ran in, except within `try_dispatch`. Every
+    /// other dispatch only reaches the listeners of `Src`: the query, fold, veto, consume,
+    /// pipeline and async dispatches, and `dispatch_range`, `dispatch_to_group`,
+    /// `dispatch_round_robin`, `dispatch_sampled`, `dispatch_traced`, `dispatch_timed`,
+    /// `dispatch_with_retry`, `try_dispatch` and `dispatch_shared`.
+    pub fn alias_event<Src: Any + 'static, Dst: Any + 'static>(&mut self, convert: impl Fn(&Src) -> Dst + 'static) {
+        let aliases = self.aliases.entry(TypeId::of::<Src>()).or_default();
+        aliases.retain(|alias| alias.target != TypeId::of::<Dst>());
+        aliases.push(Alias {
+            target: TypeId::of::<Dst>(),
+            target_name: std::any::type_name::<Dst>(),
+            convert: Box::new(move |event| match event.downcast_ref::<Src>() {
+                Some(event) => Box::new(convert(event)),
+                None => Box::new(()),
+            }),
+        });
+    }
+
+    /// Removes the `Src` to `Dst` alias, if any.
+    pub fn remove_alias<Src: Any + 'static, Dst: Any + 'static>(&mut self) {
+        if let Some(aliases) = self.aliases.get_mut(&TypeId::of::<Src>()) {
+            aliases.retain(|alias| alias.target != TypeId::of::<Dst>());
+            if aliases.is_empty() {
+                self.aliases.remove(&TypeId::of::<Src>());
+            }
+        }
+    }
+
+    // Dispatches `event` to the listeners of every type `type_id` is aliased to, asking
+    // `select`, when given, about each one as `dispatch_selected` does; positions count within
+    // each aliased type. Returns how many listeners were invoked.
+    pub(crate) fn dispatch_aliases(&mut self, type_id: TypeId, event: &dyn Any, mut select: Option<&mut dyn FnMut(usize, SubscriptionId) -> Step>) -> usize {
+        convert_aliases(&self.aliases, type_id, event)
+            .into_iter()
+            .map(|(target, target_name, event)| {
+                let select = select.as_mut().map(|select| &mut **select as &mut dyn FnMut(usize, SubscriptionId) -> Step);
+                self.dispatch_selected(target, target_name, &*event, select)
+            })
+            .sum()
+    }
+}
+
+// Builds the event for every type `type_id` is aliased to, with that type's id and name.
+pub(crate) fn convert_aliases(aliases: &HashMap<TypeId, Vec<Alias>>, type_id: TypeId, event: &dyn Any) -> Vec<(TypeId, &'static str, Box<dyn Any>)> {
+    let Some(aliases) = aliases.get(&type_id) else {
+        return Vec::new();
+    };
+    aliases.iter().map(|alias| (alias.target, alias.target_name, (alias.convert)(event))).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    // `PlayerDied` was renamed to `PlayerDefeated`; old listeners still expect the former.
+    struct PlayerDefeated {
+        player_id: u32,
+    }
+    struct PlayerDied {
+        player_id: u32,
+    }
+
+    #[test]
+    fn aliased_types_receive_converted_events() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+
+        let tx_new = tx.clone();
+        event_manager.subscribe(move |event: &PlayerDefeated| {
+            let _ = tx_new.send(("defeated", event.player_id));
+        });
+        let tx_old = tx.clone();
+        event_manager.subscribe(move |event: &PlayerDied| {
+            let _ = tx_old.send(("died", event.player_id));
+        });
+        event_manager.alias_event(|event: &PlayerDefeated| PlayerDied { player_id: event.player_id });
+
+        event_manager.dispatch(&PlayerDefeated { player_id: 3 });
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![("defeated", 3), ("died", 3)]);
+
+        // Aliases only go one way.
+        event_manager.dispatch(&PlayerDied { player_id: 4 });
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![("died", 4)]);
+
+        event_manager.remove_alias::<PlayerDefeated, PlayerDied>();
+        event_manager.dispatch(&PlayerDefeated { player_id: 5 });
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![("defeated", 5)]);
+    }
+
+    #[test]
+    fn dispatch_scoped_reaches_aliased_types_before_extra() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+
+        let tx_old = tx.clone();
+        event_manager.subscribe(move |event: &PlayerDied| {
+            let _ = tx_old.send(("died", event.player_id));
+        });
+        event_manager.alias_event(|event: &PlayerDefeated| PlayerDied { player_id: event.player_id });
+
+        event_manager.dispatch_scoped(&PlayerDefeated { player_id: 6 }, |event| {
+            let _ = tx.send(("extra", event.player_id));
+        });
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![("died", 6), ("extra", 6)]);
+    }
+
+    struct BossSlain {
+        player_id: u32,
+    }
+
+    #[test]
+    fn chained_follow_ups_follow_aliases() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+        event_manager.subscribe(move |event: &PlayerDied| {
+            let _ = tx.send(event.player_id);
+        });
+        event_manager.alias_event(|event: &PlayerDefeated| PlayerDied { player_id: event.player_id });
+        event_manager.subscribe_chaining(|event: &BossSlain, emitter| emitter.enqueue(PlayerDefeated { player_id: event.player_id }));
+
+        event_manager.dispatch(&BossSlain { player_id: 1 });
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![1]);
+
+        let mut frozen = event_manager.freeze();
+        frozen.dispatch(&BossSlain { player_id: 2 });
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![2]);
+    }
+}
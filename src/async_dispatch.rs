@@ -0,0 +1,435 @@
+use std::any::{Any, TypeId};
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+use crate::{EventManager, ListenerEntry, SubscriptionId};
+
+// The futures returned by `subscribe_async` listeners.
+pub(crate) type LocalFuture = Pin<Box<dyn Future<Output = ()>>>;
+
+// Async listeners start their work from the event and return the rest of it as a future.
+// `subscribe_async_cancelable` listeners also keep the type's current `CancelToken`.
+pub(crate) type AsyncListener = Box<dyn FnMut(&dyn Any, &CancelToken) -> Option<LocalFuture>>;
+
+/// A stateful async listener, e.g. one holding a connection pool, subscribed with
+/// `subscribe_async_handler`. Implementations can simply write
+/// `async fn handle(&self, event: &E) { .. }`.
+///
+/// The manager keeps the handler in an `Rc`, as async dispatches never leave the thread,
+/// and every dispatch awaits `handle` on a clone of it. Calls from overlapping dispatches
+/// therefore share the handler through `&self`, so state they change needs interior
+/// mutability such as a `Cell` or `RefCell`. Implemented for `Rc<H>` and `Arc<H>` as well,
+/// so the caller can keep a handle to the same handler.
+pub trait AsyncHandler<E>: 'static {
+    fn handle(&self, event: &E) -> impl Future<Output = ()>;
+}
+
+impl<E, H: AsyncHandler<E>> AsyncHandler<E> for Rc<H> {
+    fn handle(&self, event: &E) -> impl Future<Output = ()> {
+        (**self).handle(event)
+    }
+}
+
+impl<E, H: AsyncHandler<E>> AsyncHandler<E> for Arc<H> {
+    fn handle(&self, event: &E) -> impl Future<Output = ()> {
+        (**self).handle(event)
+    }
+}
+
+// Async query listeners return a `Pin<Box<dyn Future<Output = R>>>` as `Box<dyn Any>`; the
+// pool is keyed by `R`'s `TypeId` too, so the downcast back never fails.
+pub(crate) type AsyncQuery = Box<dyn FnMut(&dyn Any) -> Box<dyn Any>>;
+
+/// Tells `subscribe_async_cancelable` handlers that `cancel_all` was called for their event
+/// type. Cancellation is cooperative: nothing stops a handler that never checks
+/// `is_cancelled`, and a handler that does decides itself how to wind down. Tokens are
+/// shared atomic flags, so they can be moved to other threads.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+}
+
+impl EventManager {
+    /// Subscribes an async listener to `E`. It is called with the event and returns the
+    /// future doing the actual work, which can't borrow the event: copy what it needs out
+    /// first, e.g. `|event: &E| { let id = event.id; async move { .. } }`. Async listeners
+    /// only run through `dispatch_async` and its variants, never through `dispatch`.
+    pub fn subscribe_async<E: Any + 'static, F: Future<Output = ()> + 'static>(
+        &mut self,
+        mut listener: impl FnMut(&E) -> F + 'static,
+    ) -> SubscriptionId {
+        let id = self.allocate_id();
+        self.remember_type::<E>();
+        let boxed_listener = Box::new(move |event: &dyn Any, _: &CancelToken| {
+            event.downcast_ref::<E>().map(|event| Box::pin(listener(event)) as LocalFuture)
+        });
+        self.async_listeners
+            .entry(TypeId::of::<E>())
+            .or_default()
+            .push(ListenerEntry { id, listener: boxed_listener, retired: None });
+        id
+    }
+
+    /// Subscribes an async listener to `E` like `subscribe_async`, also handing it a
+    /// `CancelToken` that `cancel_all::<E>` cancels, e.g. for a long-running pathfinding task
+    /// that a newer target event makes pointless. Every listener started by the same
+    /// dispatch, and by any dispatch since the last `cancel_all`, shares one token.
+    pub fn subscribe_async_cancelable<E: Any + 'static, F: Future<Output = ()> + 'static>(
+        &mut self,
+        mut listener: impl FnMut(&E, CancelToken) -> F + 'static,
+    ) -> SubscriptionId {
+        let id = self.allocate_id();
+        self.remember_type::<E>();
+        let boxed_listener = Box::new(move |event: &dyn Any, token: &CancelToken| {
+            event.downcast_ref::<E>().map(|event| Box::pin(listener(event, token.clone())) as LocalFuture)
+        });
+        self.async_listeners
+            .entry(TypeId::of::<E>())
+            .or_default()
+            .push(ListenerEntry { id, listener: boxed_listener, retired: None });
+        id
+    }
+
+    /// Subscribes the async handler object `handler` to `E`; dispatches await its
+    /// `handle`, in subscription order among `E`'s other async listeners. As `handle` may
+    /// hold on to the event while it runs, each dispatch gives it its own clone of the event.
+    pub fn subscribe_async_handler<E: Any + Clone + 'static>(&mut self, handler: impl AsyncHandler<E>) -> SubscriptionId {
+        let handler = Rc::new(handler);
+        self.subscribe_async(move |event: &E| {
+            let (handler, event) = (Rc::clone(&handler), event.clone());
+            async move { handler.handle(&event).await }
+        })
+    }
+
+    /// Subscribes an async listener to `E` that answers with an `R`, for request/response
+    /// fan-out such as asking every plugin to validate an action. Like `subscribe_async`
+    /// listeners, it gets the event and returns a future that can't borrow it. Only
+    /// `dispatch_async_results` and its concurrent variant run it.
+    pub fn subscribe_async_query<E: Any + 'static, R: Any + 'static, F: Future<Output = R> + 'static>(
+        &mut self,
+        mut listener: impl FnMut(&E) -> F + 'static,
+    ) -> SubscriptionId {
+        let id = self.allocate_id();
+        self.remember_type::<E>();
+        self.remember_type::<R>();
+        let boxed_query = Box::new(move |event: &dyn Any| {
+            let event = event.downcast_ref::<E>().expect("async queries are keyed by their event type");
+            Box::new(Box::pin(listener(event)) as Pin<Box<dyn Future<Output = R>>>) as Box<dyn Any>
+        });
+        self.async_queries
+            .entry((TypeId::of::<E>(), TypeId::of::<R>()))
+            .or_default()
+            .push(ListenerEntry { id, listener: boxed_query, retired: None });
+        id
+    }
+
+    /// Asks every `subscribe_async_query` listener of `E` answering with an `R`, and returns a
+    /// future resolving to their answers in subscription order. As with `dispatch_async`,
+    /// each listener's future only starts once the previous one has finished.
+    pub fn dispatch_async_results<E: Any + 'static, R: Any + 'static>(&mut self, event: &E) -> impl Future<Output = Vec<R>> + 'static {
+        let futures = self.start_async_queries::<R>(event);
+        async move {
+            let mut results = Vec::with_capacity(futures.len());
+            for future in futures {
+                results.push(future.await);
+            }
+            results
+        }
+    }
+
+    /// Like `dispatch_async_results`, but polls every listener's future at once. The answers
+    /// are still in subscription order, not the order the futures finished in.
+    pub fn dispatch_async_results_concurrent<E: Any + 'static, R: Any + 'static>(
+        &mut self,
+        event: &E,
+    ) -> impl Future<Output = Vec<R>> + 'static {
+        JoinAll::new(self.start_async_queries::<R>(event))
+    }
+
+    /// Cancels the token of every `subscribe_async_cancelable` handler of `E` started so far.
+    /// Handlers started by later dispatches get a fresh token. This only signals: handlers
+    /// keep running until they check their token, and the futures `dispatch_async` returned
+    /// still have to be polled to completion. Handlers started on a manager that was
+    /// `merge`d into this one aren't reached.
+    pub fn cancel_all<E: Any + 'static>(&mut self) {
+        if let Some(token) = self.cancel_tokens.remove(&TypeId::of::<E>()) {
+            token.cancel();
+        }
+    }
+
+    /// Calls every async listener of `E` in subscription order and returns a future that
+    /// awaits theirs one after another, each only starting once the previous one finished.
+    /// The future resolves to how many listeners ran. It doesn't borrow the manager, so it
+    /// can be spawned on any executor. Dispatch hooks and stats don't see async dispatches.
+    pub fn dispatch_async<E: Any + 'static>(&mut self, event: &E) -> impl Future<Output = usize> + 'static {
+        let futures = self.start_async(event);
+        async move {
+            let count = futures.len();
+            for future in futures {
+                future.await;
+            }
+            count
+        }
+    }
+
+    /// Like `dispatch_async`, but the returned future polls every listener's future at once,
+    /// so they make progress concurrently on one task. It resolves once all have finished.
+    pub fn dispatch_async_concurrent<E: Any + 'static>(&mut self, event: &E) -> impl Future<Output = usize> + 'static {
+        let join = JoinAll::new(self.start_async(event));
+        async move { join.await.len() }
+    }
+
+    /// Runs `dispatch_async` to completion on the current thread with `block_on`, e.g. to
+    /// test async listeners from a plain `#[test]`. The listeners' futures run one after
+    /// another, so the outcome is deterministic. Returns how many listeners ran.
+    pub fn dispatch_async_blocking<E: Any + 'static>(&mut self, event: &E) -> usize {
+        block_on(self.dispatch_async(event))
+    }
+
+    fn start_async(&mut self, event: &dyn Any) -> Vec<LocalFuture> {
+        let type_id = event.type_id();
+        let Some(listeners) = self.async_listeners.get_mut(&type_id) else {
+            return Vec::new();
+        };
+        let token = self.cancel_tokens.entry(type_id).or_default();
+        listeners.iter_mut().filter_map(|entry| (entry.listener)(event, token)).collect()
+    }
+
+    fn start_async_queries<R: Any + 'static>(&mut self, event: &dyn Any) -> Vec<Pin<Box<dyn Future<Output = R>>>> {
+        let Some(listeners) = self.async_queries.get_mut(&(event.type_id(), TypeId::of::<R>())) else {
+            return Vec::new();
+        };
+        listeners
+            .iter_mut()
+            .map(|entry| *(entry.listener)(event).downcast().expect("async queries of this pool answer with an R"))
+            .collect()
+    }
+}
+
+// Polls every future it holds until all have completed, then yields their outputs in the
+// order the futures were given.
+struct JoinAll<T> {
+    slots: Vec<JoinSlot<T>>,
+}
+
+enum JoinSlot<T> {
+    Pending(Pin<Box<dyn Future<Output = T>>>),
+    Done(Option<T>),
+}
+
+impl<T> JoinAll<T> {
+    fn new(futures: Vec<Pin<Box<dyn Future<Output = T>>>>) -> Self {
+        JoinAll { slots: futures.into_iter().map(JoinSlot::Pending).collect() }
+    }
+}
+
+// Only the boxed futures are ever pinned, and they stay put on the heap; outputs are plain values.
+impl<T> Unpin for JoinAll<T> {}
+
+impl<T> Future for JoinAll<T> {
+    type Output = Vec<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Vec<T>> {
+        let mut done = true;
+        for slot in &mut self.slots {
+            if let JoinSlot::Pending(future) = slot {
+                match future.as_mut().poll(cx) {
+                    Poll::Ready(output) => *slot = JoinSlot::Done(Some(output)),
+                    Poll::Pending => done = false,
+                }
+            }
+        }
+        if !done {
+            return Poll::Pending;
+        }
+        let outputs = self.slots.iter_mut().map(|slot| match slot {
+            JoinSlot::Done(output) => output.take().expect("JoinAll polled after completion"),
+            JoinSlot::Pending(_) => unreachable!("every future has completed"),
+        });
+        Poll::Ready(outputs.collect())
+    }
+}
+
+// Wakes `block_on` by unparking the thread it runs on.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// A minimal executor: drives `future` to completion on the current thread, parking it
+/// while the future waits. Enough for tests and simple tools, and what
+/// `EventManager::dispatch_async_blocking` runs on; use a real runtime for futures that
+/// need its reactor, such as tokio's sockets and timers.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        thread::park();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    struct RequestReceived(u32);
+
+    // Returns `Pending` once, like a future waiting on I/O that is immediately ready.
+    struct YieldOnce(bool);
+
+    impl Future for YieldOnce {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                return Poll::Ready(());
+            }
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    fn handler(event_manager: &mut EventManager, tx: &mpsc::Sender<String>, name: &'static str) {
+        let tx = tx.clone();
+        event_manager.subscribe_async(move |event: &RequestReceived| {
+            let (tx, request) = (tx.clone(), event.0);
+            async move {
+                let _ = tx.send(format!("{name} starts {request}"));
+                YieldOnce(false).await;
+                let _ = tx.send(format!("{name} ends {request}"));
+            }
+        });
+    }
+
+    #[test]
+    fn async_listeners_run_sequentially_or_concurrently() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+        handler(&mut event_manager, &tx, "auth");
+        handler(&mut event_manager, &tx, "log");
+        event_manager.subscribe(|_: &RequestReceived| panic!("plain listeners aren't awaited"));
+
+        assert_eq!(event_manager.dispatch_async_blocking(&RequestReceived(1)), 2);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["auth starts 1", "auth ends 1", "log starts 1", "log ends 1"]);
+
+        assert_eq!(block_on(event_manager.dispatch_async_concurrent(&RequestReceived(2))), 2);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["auth starts 2", "log starts 2", "auth ends 2", "log ends 2"]);
+        assert_eq!(event_manager.type_count(), 1);
+    }
+
+    #[test]
+    fn cancel_all_signals_running_handlers() {
+        struct TargetChanged;
+
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+        event_manager.subscribe_async_cancelable(move |_: &TargetChanged, token: CancelToken| {
+            let tx = tx.clone();
+            async move {
+                let mut steps = 0;
+                while !token.is_cancelled() {
+                    steps += 1;
+                    YieldOnce(false).await;
+                }
+                let _ = tx.send(steps);
+            }
+        });
+
+        let mut search = Box::pin(event_manager.dispatch_async(&TargetChanged));
+        let mut cx = Context::from_waker(Waker::noop());
+        for _ in 0..3 {
+            assert!(search.as_mut().poll(&mut cx).is_pending());
+        }
+        event_manager.cancel_all::<TargetChanged>();
+        assert_eq!(block_on(search), 1);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![3]);
+
+        // Later dispatches start with a fresh token.
+        let mut next = Box::pin(event_manager.dispatch_async(&TargetChanged));
+        assert!(next.as_mut().poll(&mut cx).is_pending());
+        event_manager.cancel_all::<TargetChanged>();
+        assert_eq!(block_on(next), 1);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![1]);
+    }
+
+    #[test]
+    fn async_queries_answer_in_subscription_order() {
+        struct ValidateMove(i32);
+
+        let mut event_manager = EventManager::new();
+        // The slow plugin finishes last but still answers first.
+        event_manager.subscribe_async_query(|event: &ValidateMove| {
+            let distance = event.0;
+            async move {
+                YieldOnce(false).await;
+                YieldOnce(false).await;
+                distance <= 5
+            }
+        });
+        event_manager.subscribe_async_query(|event: &ValidateMove| {
+            let distance = event.0;
+            async move { distance >= 0 }
+        });
+        event_manager.subscribe_async_query(|_: &ValidateMove| async { "unrelated answer type" });
+
+        assert_eq!(block_on(event_manager.dispatch_async_results::<_, bool>(&ValidateMove(7))), vec![false, true]);
+        assert_eq!(block_on(event_manager.dispatch_async_results_concurrent::<_, bool>(&ValidateMove(3))), vec![true, true]);
+        assert_eq!(block_on(event_manager.dispatch_async_results::<_, u8>(&ValidateMove(3))), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn handler_objects_keep_their_state() {
+        use std::cell::Cell;
+
+        #[derive(Clone)]
+        struct Query(&'static str);
+
+        #[derive(Default)]
+        struct CountingHandler {
+            handled: Cell<u32>,
+        }
+
+        impl AsyncHandler<Query> for CountingHandler {
+            async fn handle(&self, event: &Query) {
+                YieldOnce(false).await;
+                assert!(!event.0.is_empty());
+                self.handled.set(self.handled.get() + 1);
+            }
+        }
+
+        let mut event_manager = EventManager::new();
+        let handler = Rc::new(CountingHandler::default());
+        event_manager.subscribe_async_handler(Rc::clone(&handler));
+
+        assert_eq!(event_manager.dispatch_async_blocking(&Query("select")), 1);
+        let overlapping = [event_manager.dispatch_async(&Query("insert")), event_manager.dispatch_async(&Query("delete"))];
+        for dispatch in overlapping {
+            assert_eq!(block_on(dispatch), 1);
+        }
+        assert_eq!(handler.handled.get(), 3);
+    }
+}
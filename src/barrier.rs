@@ -0,0 +1,108 @@
+use std::any::{Any, TypeId};
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use crate::EventManager;
+
+// Shared between the internal listeners installed by `wait_for_all`.
+struct Barrier {
+    pending: HashSet<TypeId>,
+    callback: Option<Box<dyn FnOnce()>>,
+}
+
+impl EventManager {
+    /// Runs `once` exactly once, as soon as every event type in `types` has been dispatched
+    /// at least once since this call. Types may fire any number of times and in any order;
+    /// an already-satisfied type is simply ignored until the rest arrive.
+    /// Each internal listener removes itself once its type is satisfied.
+    /// If `types` is empty, `once` runs immediately.
+    pub fn wait_for_all(&mut self, types: &[TypeId], once: impl FnOnce() + 'static) {
+        let pending: HashSet<TypeId> = types.iter().copied().collect();
+        if pending.is_empty() {
+            once();
+            return;
+        }
+
+        let barrier = Rc::new(RefCell::new(Barrier {
+            pending: pending.clone(),
+            callback: Some(Box::new(once)),
+        }));
+
+        for type_id in pending {
+            let barrier = Rc::clone(&barrier);
+            let retired = Rc::new(Cell::new(false));
+            let retired_flag = Rc::clone(&retired);
+
+            let listener = Box::new(move |_: &dyn Any| {
+                retired_flag.set(true);
+                let callback = {
+                    let mut barrier = barrier.borrow_mut();
+                    barrier.pending.remove(&type_id);
+                    if barrier.pending.is_empty() {
+                        barrier.callback.take()
+                    } else {
+                        None
+                    }
+                };
+                // The borrow is released before running user code.
+                if let Some(callback) = callback {
+                    callback();
+                }
+            });
+
+            self.push_listener(type_id, listener, Some(retired));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    struct QuestAccepted;
+    struct ItemFound;
+    struct BossDefeated;
+
+    #[test]
+    fn wait_for_all_fires_once_after_every_event() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<()>();
+
+        event_manager.wait_for_all(
+            &[TypeId::of::<QuestAccepted>(), TypeId::of::<ItemFound>(), TypeId::of::<BossDefeated>()],
+            move || {
+                let _ = tx.send(());
+            },
+        );
+
+        event_manager.dispatch(&ItemFound);
+        event_manager.dispatch(&ItemFound);
+        event_manager.dispatch(&BossDefeated);
+        assert!(rx.try_recv().is_err(), "Barrier fired before all events arrived");
+
+        event_manager.dispatch(&QuestAccepted);
+        assert!(rx.try_recv().is_ok(), "Barrier should fire on the final event");
+
+        event_manager.dispatch(&QuestAccepted);
+        event_manager.dispatch(&ItemFound);
+        event_manager.dispatch(&BossDefeated);
+        assert!(rx.try_recv().is_err(), "Barrier should fire only once");
+
+        // Every internal listener has cleaned up after itself.
+        assert!(event_manager.listeners.iter().all(|(_, bucket)| bucket.is_empty()));
+    }
+
+    #[test]
+    fn wait_for_all_with_no_types_fires_immediately() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<()>();
+
+        event_manager.wait_for_all(&[], move || {
+            let _ = tx.send(());
+        });
+
+        assert!(rx.try_recv().is_ok());
+    }
+}
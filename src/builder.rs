@@ -0,0 +1,79 @@
+use std::any::Any;
+
+use crate::EventManager;
+
+/// Wires up a whole `EventManager` in one expression, e.g. for tests and examples:
+/// `EventManager::builder().on(|_: &A| ..).on(|_: &B| ..).build()`.
+/// Each `on` is the same as a `subscribe` on the finished manager, in the same order.
+pub struct EventManagerBuilder {
+    event_manager: EventManager,
+}
+
+impl Default for EventManagerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventManagerBuilder {
+    pub fn new() -> Self {
+        EventManagerBuilder { event_manager: EventManager::new() }
+    }
+
+    /// Subscribes `listener` to `E`.
+    pub fn on<E: Any + 'static>(mut self, listener: impl FnMut(&E) + 'static) -> Self {
+        self.event_manager.subscribe(listener);
+        self
+    }
+
+    /// Subscribes the read-only `listener` to `E`, like `subscribe_fn`.
+    pub fn on_fn<E: Any + 'static>(mut self, listener: impl Fn(&E) + 'static) -> Self {
+        self.event_manager.subscribe_fn(listener);
+        self
+    }
+
+    pub fn build(self) -> EventManager {
+        self.event_manager
+    }
+}
+
+impl EventManager {
+    /// Starts an `EventManagerBuilder`.
+    pub fn builder() -> EventManagerBuilder {
+        EventManagerBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    struct Connected;
+    struct MessageReceived(&'static str);
+    struct Disconnected;
+
+    #[test]
+    fn builder_wires_every_type() {
+        let (tx, rx) = mpsc::channel();
+        let (tx_message, tx_disconnect) = (tx.clone(), tx.clone());
+
+        let mut event_manager = EventManager::builder()
+            .on(move |_: &Connected| {
+                let _ = tx.send("connected");
+            })
+            .on(move |message: &MessageReceived| {
+                let _ = tx_message.send(message.0);
+            })
+            .on_fn(move |_: &Disconnected| {
+                let _ = tx_disconnect.send("disconnected");
+            })
+            .build();
+
+        event_manager.dispatch(&Connected);
+        event_manager.dispatch(&MessageReceived("hello"));
+        event_manager.dispatch(&Disconnected);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["connected", "hello", "disconnected"]);
+        assert_eq!(event_manager.type_count(), 3);
+    }
+}
@@ -0,0 +1,103 @@
+use std::any::TypeId;
+
+use crate::{EventManager, SubscriptionId};
+
+/// What `EventManager::capacity_report` found, for spotting memory kept by listener churn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapacityReport {
+    /// One entry per event type with a bucket, sorted by type name.
+    pub types: Vec<TypeCapacity>,
+    /// How many types the listener store can hold without growing, if the store can tell
+    /// (a `HashMap` store can, a `BTreeMap` can't).
+    pub store_capacity: Option<usize>,
+}
+
+/// The `subscribe` listeners of one event type in a `CapacityReport`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeCapacity {
+    pub event: &'static str,
+    /// Listeners held, including self-removed ones not yet pruned.
+    pub listeners: usize,
+    /// Listeners the bucket can hold without growing.
+    pub capacity: usize,
+    /// The heap size of the boxed closures, not counting anything they own indirectly.
+    pub closure_bytes: usize,
+}
+
+impl EventManager {
+    /// Reports, per event type, how many `subscribe` listeners are held against how many
+    /// the bucket has room for, plus the capacity of the store itself. Capacity well above
+    /// the count after heavy churn is what `shrink_to_fit` releases.
+    pub fn capacity_report(&self) -> CapacityReport {
+        let mut types: Vec<TypeCapacity> = self
+            .listeners
+            .iter()
+            .map(|(type_id, bucket)| TypeCapacity {
+                event: self.type_name_of(*type_id),
+                listeners: bucket.len(),
+                capacity: bucket.capacity(),
+                closure_bytes: bucket.closure_bytes(),
+            })
+            .collect();
+        types.sort_by_key(|entry| entry.event);
+        CapacityReport { types, store_capacity: self.listeners.capacity() }
+    }
+
+    /// Releases the spare capacity of every listener bucket and of the store, dropping
+    /// self-removed listeners and the buckets left empty.
+    pub fn shrink_to_fit(&mut self) {
+        let mut emptied: Vec<TypeId> = Vec::new();
+        let mut pruned: Vec<(TypeId, Vec<SubscriptionId>)> = Vec::new();
+        for (type_id, mut bucket) in self.listeners.drain() {
+            pruned.push((type_id, bucket.prune_retired()));
+            if bucket.is_empty() {
+                emptied.push(type_id);
+                continue;
+            }
+            bucket.shrink_to_fit();
+            *self.listeners.get_or_default(type_id) = bucket;
+        }
+        self.listeners.shrink_to_fit();
+        for (type_id, ids) in pruned {
+            self.forget_listeners(type_id, &ids);
+        }
+        for type_id in emptied {
+            self.sync_lifecycle(type_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Particle([u64; 4]);
+
+    #[test]
+    fn shrink_to_fit_releases_churned_capacity() {
+        let mut event_manager = EventManager::new();
+        let ids: Vec<_> = (0..100u64)
+            .map(|seed| {
+                event_manager.subscribe(move |particle: &Particle| {
+                    std::hint::black_box(particle.0[0] + seed);
+                })
+            })
+            .collect();
+        for id in &ids[1..] {
+            event_manager.unsubscribe(*id);
+        }
+
+        let report = event_manager.capacity_report();
+        assert_eq!(report.types.len(), 1);
+        let particles = &report.types[0];
+        assert!(particles.event.ends_with("Particle"));
+        assert_eq!(particles.listeners, 1);
+        assert!(particles.capacity >= 100);
+        assert_eq!(particles.closure_bytes, std::mem::size_of::<u64>());
+        assert!(report.store_capacity.is_some_and(|capacity| capacity >= 1));
+
+        event_manager.shrink_to_fit();
+        let report = event_manager.capacity_report();
+        assert_eq!(report.types[0].capacity, 1);
+    }
+}
@@ -0,0 +1,216 @@
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::{EventManager, SubscriptionId};
+
+/// A handle given to `subscribe_chaining` listeners for raising follow-up events.
+/// It can only queue events; they are dispatched once the current dispatch has finished,
+/// or with the manager's deferred queue when raised with `defer`.
+#[derive(Default)]
+pub struct EventEmitter {
+    pending: Vec<(TypeId, &'static str, Box<dyn Any>)>,
+    deferred: Vec<(TypeId, &'static str, Box<dyn Any>)>,
+}
+
+impl EventEmitter {
+    /// Queues `event` to be dispatched after the event currently being handled.
+    pub fn enqueue<E: Any + 'static>(&mut self, event: E) {
+        self.pending.push((TypeId::of::<E>(), std::any::type_name::<E>(), Box::new(event)));
+    }
+
+    /// Adds `event` to the manager's deferred queue, as if passed to `EventManager::queue`,
+    /// so it waits for the next `flush` rather than following the current dispatch.
+    pub fn defer<E: Any + 'static>(&mut self, event: E) {
+        self.deferred.push((TypeId::of::<E>(), std::any::type_name::<E>(), Box::new(event)));
+    }
+
+    pub(crate) fn deferred_len(&self) -> usize {
+        self.deferred.len()
+    }
+}
+
+/// Errors from `try_dispatch`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DispatchError {
+    /// A follow-up event had the same type as one of the events that led to it. Holds the
+    /// type names from the first occurrence of that type down to the repeated one, e.g.
+    /// `[A, B, A]`.
+    Cycle(Vec<&'static str>),
+}
+
+impl fmt::Display for DispatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DispatchError::Cycle(chain) => write!(f, "chained events form a cycle: {}", chain.join(" -> ")),
+        }
+    }
+}
+
+impl std::error::Error for DispatchError {}
+
+// A queued follow-up event, with the types of the events that led to it, root first.
+type TracedEvent = (Vec<(TypeId, &'static str)>, TypeId, &'static str, Box<dyn Any>);
+
+impl EventManager {
+    /// Subscribes a listener to `E` that can raise follow-up events through an `EventEmitter`.
+    /// Whatever it enqueues is dispatched right after the dispatch that triggered it, in
+    /// enqueue order, and follow-ups may enqueue further events in turn. A chain that never
+    /// stops enqueueing keeps the outer `dispatch` from returning; `try_dispatch` detects such loops.
+    pub fn subscribe_chaining<E: Any + 'static>(
+        &mut self,
+        mut listener: impl FnMut(&E, &mut EventEmitter) + 'static,
+    ) -> SubscriptionId {
+        if self.outboxes.is_empty() {
+            self.outboxes.push(Rc::default());
+        }
+        let outbox = Rc::clone(&self.outboxes[0]);
+        // Listeners can't dispatch, so nothing else borrows the outbox while this runs.
+        self.subscribe(move |event: &E| listener(event, &mut outbox.borrow_mut()))
+    }
+
+    /// Dispatches `event` like `dispatch`, but stops following chained events as soon as one
+    /// would be dispatched as a consequence of an event of its own type, and reports the
+    /// types involved instead of looping. The offending event and every follow-up still
+    /// queued are dropped. Follow-ups are dispatched in the same order as with `dispatch`.
+    pub fn try_dispatch<E: Any + 'static>(&mut self, event: &E) -> Result<(), DispatchError> {
+        let root = (TypeId::of::<E>(), std::any::type_name::<E>());
+        self.dispatch_selected(root.0, root.1, event, None);
+
+        let mut queue: VecDeque<TracedEvent> = VecDeque::new();
+        for (type_id, type_name, event) in take_pending(&self.outboxes) {
+            queue.push_back((vec![root], type_id, type_name, event));
+        }
+        while let Some((mut trail, type_id, type_name, event)) = queue.pop_front() {
+            if let Some(first) = trail.iter().position(|(seen, _)| *seen == type_id) {
+                let mut chain: Vec<&'static str> = trail[first..].iter().map(|(_, name)| *name).collect();
+                chain.push(type_name);
+                return Err(DispatchError::Cycle(chain));
+            }
+            self.dispatch_selected(type_id, type_name, &*event, None);
+            trail.push((type_id, type_name));
+            // Taking the outboxes after each event attributes every follow-up to its cause.
+            for (type_id, type_name, event) in take_pending(&self.outboxes) {
+                queue.push_back((trail.clone(), type_id, type_name, event));
+            }
+        }
+        Ok(())
+    }
+
+    // Dispatches everything chaining listeners enqueued, following aliases like `dispatch`,
+    // until no more follow-ups arrive.
+    pub(crate) fn flush_chained(&mut self) {
+        loop {
+            let batch = take_pending(&self.outboxes);
+            if batch.is_empty() {
+                return;
+            }
+            for (type_id, type_name, event) in batch {
+                self.dispatch_selected(type_id, type_name, &*event, None);
+                if !self.aliases.is_empty() {
+                    self.dispatch_aliases(type_id, &*event, None);
+                }
+            }
+        }
+    }
+}
+
+// Empties every outbox, oldest manager's first.
+pub(crate) fn take_pending(outboxes: &[Rc<RefCell<EventEmitter>>]) -> Vec<(TypeId, &'static str, Box<dyn Any>)> {
+    let mut batch = Vec::new();
+    for outbox in outboxes {
+        batch.append(&mut outbox.borrow_mut().pending);
+    }
+    batch
+}
+
+// Empties the deferred part of every outbox, oldest manager's first.
+pub(crate) fn take_deferred(outboxes: &[Rc<RefCell<EventEmitter>>]) -> Vec<(TypeId, &'static str, Box<dyn Any>)> {
+    let mut batch = Vec::new();
+    for outbox in outboxes {
+        batch.append(&mut outbox.borrow_mut().deferred);
+    }
+    batch
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    struct EnemyKilled {
+        points: u32,
+    }
+    struct ScoreChanged(u32);
+    struct HighScore(u32);
+
+    #[test]
+    fn chained_events_are_dispatched_after_the_current_one() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<String>();
+
+        let mut score = 0;
+        event_manager.subscribe_chaining(move |event: &EnemyKilled, emitter| {
+            score += event.points;
+            emitter.enqueue(ScoreChanged(score));
+        });
+        event_manager.subscribe_chaining(|event: &ScoreChanged, emitter| {
+            if event.0 >= 100 {
+                emitter.enqueue(HighScore(event.0));
+            }
+        });
+        let tx_kill = tx.clone();
+        event_manager.subscribe(move |_: &EnemyKilled| {
+            let _ = tx_kill.send("killed".to_string());
+        });
+        let tx_score = tx.clone();
+        event_manager.subscribe(move |event: &ScoreChanged| {
+            let _ = tx_score.send(format!("score {}", event.0));
+        });
+        let tx_high = tx.clone();
+        event_manager.subscribe(move |event: &HighScore| {
+            let _ = tx_high.send(format!("high score {}", event.0));
+        });
+
+        event_manager.dispatch(&EnemyKilled { points: 60 });
+        event_manager.dispatch(&EnemyKilled { points: 60 });
+
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            vec!["killed", "score 60", "killed", "score 120", "high score 120"]
+        );
+    }
+
+    struct Ping;
+    struct Pong;
+
+    #[test]
+    fn try_dispatch_reports_the_cycle() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<&'static str>();
+
+        event_manager.subscribe_chaining(|_: &Ping, emitter| emitter.enqueue(Pong));
+        event_manager.subscribe_chaining(|_: &Pong, emitter| emitter.enqueue(Ping));
+        let tx_ping = tx.clone();
+        event_manager.subscribe(move |_: &Ping| {
+            let _ = tx_ping.send("ping");
+        });
+        let tx_pong = tx.clone();
+        event_manager.subscribe(move |_: &Pong| {
+            let _ = tx_pong.send("pong");
+        });
+
+        let Err(DispatchError::Cycle(chain)) = event_manager.try_dispatch(&Ping) else {
+            panic!("expected a cycle");
+        };
+        let short: Vec<&str> = chain.iter().map(|name| name.rsplit("::").next().unwrap()).collect();
+        assert_eq!(short, vec!["Ping", "Pong", "Ping"]);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["ping", "pong"]);
+
+        // Chains without a repeated type dispatch normally.
+        event_manager.drain_type::<Pong>();
+        assert_eq!(event_manager.try_dispatch(&Ping), Ok(()));
+    }
+}
@@ -0,0 +1,270 @@
+use std::any::{Any, TypeId};
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::{EventManager, SubscriptionId};
+
+/// What a bounded channel listener does with an event when its channel is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backpressure {
+    /// Wait inside `dispatch` until the receiver makes room. This stalls the whole
+    /// dispatching thread, so only use it when the consumer runs on another thread and is
+    /// known to keep up eventually.
+    Block,
+    /// Discard the incoming event.
+    DropNewest,
+    /// Discard the oldest queued event to make room for the incoming one.
+    DropOldest,
+}
+
+struct ChannelState<E> {
+    queue: VecDeque<E>,
+    dropped: u64,
+    receiver_alive: bool,
+}
+
+struct Channel<E> {
+    state: Mutex<ChannelState<E>>,
+    // Signalled when an event is queued or taken.
+    changed: Condvar,
+}
+
+impl<E> Channel<E> {
+    fn lock(&self) -> MutexGuard<'_, ChannelState<E>> {
+        // A panic while holding the lock can't leave the queue half-updated.
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// The receiving end of `EventManager::subscribe_bounded_channel`. It can be sent to
+/// another thread; dropping it removes the listener on the next dispatch.
+pub struct BoundedReceiver<E> {
+    channel: Arc<Channel<E>>,
+}
+
+impl<E> BoundedReceiver<E> {
+    /// Takes the oldest queued event, if any, without waiting.
+    pub fn try_recv(&self) -> Option<E> {
+        let event = self.channel.lock().queue.pop_front();
+        if event.is_some() {
+            self.channel.changed.notify_all();
+        }
+        event
+    }
+
+    /// Waits up to `timeout` for an event.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<E> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.channel.lock();
+        loop {
+            if let Some(event) = state.queue.pop_front() {
+                self.channel.changed.notify_all();
+                return Some(event);
+            }
+            let remaining = deadline.checked_duration_since(Instant::now())?;
+            state = self
+                .channel
+                .changed
+                .wait_timeout(state, remaining)
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .0;
+        }
+    }
+
+    /// Returns how many events are queued.
+    pub fn len(&self) -> usize {
+        self.channel.lock().queue.len()
+    }
+
+    /// Returns true if no events are queued.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns how many events the drop policies have discarded so far.
+    pub fn dropped(&self) -> u64 {
+        self.channel.lock().dropped
+    }
+}
+
+impl<E> Drop for BoundedReceiver<E> {
+    fn drop(&mut self) {
+        self.channel.lock().receiver_alive = false;
+        // Wake a listener blocked on a full queue so it can give up.
+        self.channel.changed.notify_all();
+    }
+}
+
+impl EventManager {
+    /// Subscribes a listener that forwards clones of every `E` into a channel holding at
+    /// most `capacity` events, and returns its receiving end. `policy` decides what happens
+    /// when the consumer falls behind and the channel is full.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn subscribe_bounded_channel<E: Any + Clone + Send + 'static>(
+        &mut self,
+        capacity: usize,
+        policy: Backpressure,
+    ) -> BoundedReceiver<E> {
+        assert!(capacity > 0, "a bounded channel needs room for at least one event");
+        let channel = Arc::new(Channel {
+            state: Mutex::new(ChannelState {
+                queue: VecDeque::with_capacity(capacity),
+                dropped: 0,
+                receiver_alive: true,
+            }),
+            changed: Condvar::new(),
+        });
+
+        let sender = Arc::clone(&channel);
+        let retired = Rc::new(Cell::new(false));
+        let retired_flag = Rc::clone(&retired);
+        let listener = Box::new(move |event: &dyn Any| {
+            let Some(event) = event.downcast_ref::<E>() else {
+                return;
+            };
+            let mut state = sender.lock();
+            if policy == Backpressure::Block {
+                while state.queue.len() >= capacity && state.receiver_alive {
+                    state = sender.changed.wait(state).unwrap_or_else(|poisoned| poisoned.into_inner());
+                }
+            }
+            if !state.receiver_alive {
+                retired_flag.set(true);
+                return;
+            }
+            if state.queue.len() >= capacity {
+                state.dropped += 1;
+                match policy {
+                    Backpressure::DropNewest => return,
+                    Backpressure::DropOldest | Backpressure::Block => {
+                        state.queue.pop_front();
+                    }
+                }
+            }
+            state.queue.push_back(event.clone());
+            drop(state);
+            sender.changed.notify_all();
+        });
+        self.remember_type::<E>();
+        self.push_listener(TypeId::of::<E>(), listener, Some(retired));
+
+        BoundedReceiver { channel }
+    }
+
+    /// Forwards every future `E` dispatched here into `sender`, so another thread can do the
+    /// processing, e.g. with `pump_from` on its own manager. The forwarder is one listener
+    /// among the others; pass its id to `unsubscribe` to stop forwarding. It also removes
+    /// itself once the receiving end is dropped.
+    pub fn drain_all_into<E: Any + Clone + 'static>(&mut self, sender: Sender<E>) -> SubscriptionId {
+        let retired = Rc::new(Cell::new(false));
+        let retired_flag = Rc::clone(&retired);
+        let listener = Box::new(move |event: &dyn Any| {
+            let Some(event) = event.downcast_ref::<E>() else {
+                return;
+            };
+            if sender.send(event.clone()).is_err() {
+                retired_flag.set(true);
+            }
+        });
+        self.remember_type::<E>();
+        self.push_listener(TypeId::of::<E>(), listener, Some(retired))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::thread;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Telemetry(u32);
+
+    fn dispatch_all(event_manager: &mut EventManager, values: impl IntoIterator<Item = u32>) {
+        for value in values {
+            event_manager.dispatch(&Telemetry(value));
+        }
+    }
+
+    #[test]
+    fn drop_policies_at_capacity() {
+        let mut event_manager = EventManager::new();
+        let newest = event_manager.subscribe_bounded_channel::<Telemetry>(2, Backpressure::DropNewest);
+        let oldest = event_manager.subscribe_bounded_channel::<Telemetry>(2, Backpressure::DropOldest);
+
+        dispatch_all(&mut event_manager, 1..=4);
+
+        assert_eq!(newest.len(), 2);
+        assert_eq!(newest.dropped(), 2);
+        assert_eq!(newest.try_recv(), Some(Telemetry(1)));
+        assert_eq!(newest.try_recv(), Some(Telemetry(2)));
+        assert_eq!(newest.try_recv(), None);
+
+        assert_eq!(oldest.dropped(), 2);
+        assert_eq!(oldest.try_recv(), Some(Telemetry(3)));
+        assert_eq!(oldest.try_recv(), Some(Telemetry(4)));
+
+        // Dropping a receiver unsubscribes its listener.
+        drop(newest);
+        dispatch_all(&mut event_manager, [5]);
+        assert_eq!(event_manager.live_listener_count(TypeId::of::<Telemetry>()), 1);
+    }
+
+    #[test]
+    fn block_policy_waits_for_the_consumer() {
+        let mut event_manager = EventManager::new();
+        let rx = event_manager.subscribe_bounded_channel::<Telemetry>(1, Backpressure::Block);
+
+        let consumer = thread::spawn(move || {
+            (0..3)
+                .map(|_| rx.recv_timeout(Duration::from_secs(5)).expect("producer stalled"))
+                .collect::<Vec<_>>()
+        });
+        dispatch_all(&mut event_manager, 1..=3);
+
+        assert_eq!(consumer.join().unwrap(), vec![Telemetry(1), Telemetry(2), Telemetry(3)]);
+    }
+
+    #[test]
+    fn drain_all_into_feeds_a_processing_thread() {
+        let mut producer = EventManager::new();
+        let (tx, rx) = mpsc::channel::<Telemetry>();
+        let forwarder = producer.drain_all_into(tx);
+
+        let consumer = thread::spawn(move || {
+            let mut processing = EventManager::new();
+            let (done_tx, done_rx) = mpsc::channel();
+            processing.subscribe(move |event: &Telemetry| {
+                let _ = done_tx.send(event.0);
+            });
+            // Runs until the producer's forwarder, and with it the sender, is gone.
+            while let Ok(event) = rx.recv() {
+                processing.dispatch(&event);
+            }
+            done_rx.try_iter().collect::<Vec<_>>()
+        });
+
+        dispatch_all(&mut producer, [1, 2, 3]);
+        assert!(producer.unsubscribe(forwarder));
+        dispatch_all(&mut producer, [4]);
+        assert_eq!(consumer.join().unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn forwarder_removes_itself_when_the_receiver_is_gone() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<Telemetry>();
+        event_manager.drain_all_into(tx);
+        drop(rx);
+
+        dispatch_all(&mut event_manager, [1]);
+        assert!(!event_manager.has_listeners::<Telemetry>());
+    }
+}
@@ -0,0 +1,166 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::{EventManager, Listener, ListenerEntry, SubscriptionId};
+
+// Builds a fresh instance of a `subscribe_cloneable` listener.
+pub(crate) type ListenerFactory = Rc<dyn Fn() -> Listener>;
+
+impl EventManager {
+    /// Subscribes the listener built by `factory` to `E`, keeping the factory so that cloning
+    /// the manager can build a fresh, independent instance of the listener for the clone.
+    pub fn subscribe_cloneable<E: Any + 'static>(&mut self, factory: impl Fn() -> Box<dyn FnMut(&E)> + 'static) -> SubscriptionId {
+        let type_id = TypeId::of::<E>();
+        let factory: ListenerFactory = Rc::new(move || {
+            let mut listener = factory();
+            Box::new(move |event: &dyn Any| {
+                if let Some(event) = event.downcast_ref::<E>() {
+                    listener(event);
+                }
+            })
+        });
+        self.remember_type::<E>();
+        let id = self.push_listener(type_id, factory(), None);
+
+        // Factories of listeners that have since been removed are no longer needed.
+        let factories = self.factories.entry(type_id).or_default();
+        let bucket = self.listeners.get(&type_id);
+        factories.retain(|(id, _)| bucket.is_some_and(|bucket| bucket.contains_live(*id)));
+        factories.push((id, factory));
+        id
+    }
+}
+
+/// Clones the manager by re-running the factories of its `subscribe_cloneable` listeners,
+/// so both managers dispatch independently. Only those listeners survive cloning, keeping
+/// their ids, phases, priorities, tags, names and ordering constraints, so they run in the
+/// same order as here. All other listeners, hooks and settings apart from the phase and tag
+/// order are left behind, and the clone keeps its listeners in a default `HashMap` store.
+impl Clone for EventManager {
+    fn clone(&self) -> Self {
+        let mut clone = EventManager::new();
+        clone.next_id = self.next_id;
+        clone.ids_wrapped = self.ids_wrapped;
+        clone.phase_order = self.phase_order.clone();
+        clone.tag_order = self.tag_order.clone();
+        clone.type_names = self.type_names.clone();
+        for (&type_id, factories) in &self.factories {
+            let Some(bucket) = self.listeners.get(&type_id) else {
+                continue;
+            };
+            // In dispatch order, so listeners the metadata doesn't separate keep their order.
+            let surviving: Vec<SubscriptionId> = bucket.live_ids().filter(|id| factories.iter().any(|(factory_id, _)| factory_id == id)).collect();
+            for &id in &surviving {
+                let (_, factory) = factories.iter().find(|(factory_id, _)| *factory_id == id).expect("filtered to ids with a factory");
+                clone.insert_listener(type_id, ListenerEntry { id, listener: factory(), retired: None });
+                clone.factories.entry(type_id).or_default().push((id, Rc::clone(factory)));
+            }
+            copy_surviving(&self.phases, &mut clone.phases, type_id, &surviving);
+            copy_surviving(&self.priorities, &mut clone.priorities, type_id, &surviving);
+            copy_surviving(&self.tags, &mut clone.tags, type_id, &surviving);
+            copy_surviving(&self.names, &mut clone.names, type_id, &surviving);
+            if let Some(constraints) = self.run_after.get(&type_id) {
+                let kept = constraints.iter().filter(|(first, then)| surviving.contains(first) && surviving.contains(then));
+                clone.run_after.insert(type_id, kept.copied().collect());
+            }
+            // The constraints held here already, so they can't form a cycle in the clone.
+            let _ = clone.reorder(type_id);
+        }
+        clone
+    }
+}
+
+// Copies the entries of `from[type_id]` that belong to `surviving` listeners into `into`.
+fn copy_surviving<V: Clone>(
+    from: &HashMap<TypeId, HashMap<SubscriptionId, V>>,
+    into: &mut HashMap<TypeId, HashMap<SubscriptionId, V>>,
+    type_id: TypeId,
+    surviving: &[SubscriptionId],
+) {
+    if let Some(values) = from.get(&type_id) {
+        let kept = values.iter().filter(|(id, _)| surviving.contains(id));
+        into.insert(type_id, kept.map(|(id, value)| (*id, value.clone())).collect());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Phase;
+    use std::sync::mpsc;
+
+    struct CoinCollected;
+
+    // Each listener instance reports its own running total.
+    fn coin_counter(tx: &mpsc::Sender<u32>) -> impl Fn() -> Box<dyn FnMut(&CoinCollected)> {
+        let tx = tx.clone();
+        move || {
+            let tx = tx.clone();
+            let mut collected = 0;
+            Box::new(move |_: &CoinCollected| {
+                collected += 1;
+                let _ = tx.send(collected);
+            })
+        }
+    }
+
+    #[test]
+    fn clones_dispatch_independently() {
+        let mut session = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+        let counter = session.subscribe_cloneable(coin_counter(&tx));
+        let tx_plain = tx.clone();
+        session.subscribe(move |_: &CoinCollected| {
+            let _ = tx_plain.send(100);
+        });
+
+        session.dispatch(&CoinCollected);
+        session.dispatch(&CoinCollected);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![1, 100, 2, 100]);
+
+        let mut other = session.clone();
+        assert_eq!(other.listener_ids::<CoinCollected>(), vec![counter]);
+        other.dispatch(&CoinCollected);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![1], "the clone starts from a fresh instance");
+
+        session.dispatch(&CoinCollected);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![3, 100]);
+    }
+
+    #[test]
+    fn clones_keep_phase_and_priority_order() {
+        let mut session = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+        let reporter = |value: u32| {
+            let tx = tx.clone();
+            move || {
+                let tx = tx.clone();
+                Box::new(move |_: &CoinCollected| {
+                    let _ = tx.send(value);
+                }) as Box<dyn FnMut(&CoinCollected)>
+            }
+        };
+
+        let late = session.subscribe_cloneable(reporter(4));
+        let urgent = session.subscribe_cloneable(reporter(2));
+        let early = session.subscribe_cloneable(reporter(1));
+        let follower = session.subscribe_cloneable(reporter(3));
+        session.order_after::<CoinCollected>(follower, urgent).unwrap();
+        // Set the metadata the way `subscribe_in_phase` and `subscribe_with_priority` record it.
+        let type_id = TypeId::of::<CoinCollected>();
+        session.phases.entry(type_id).or_default().extend([(late, Phase::PostUpdate), (early, Phase::PreUpdate)]);
+        session.priorities.entry(type_id).or_default().insert(urgent, 10);
+        session.reorder(type_id).unwrap();
+
+        let mut other = session.clone();
+        session.dispatch(&CoinCollected);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        other.dispatch(&CoinCollected);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+
+        // The metadata came along too, so later subscriptions slot in the same way.
+        other.subscribe_in_phase(Phase::PreUpdate, |_: &CoinCollected| {});
+        assert_eq!(other.listener_ids::<CoinCollected>()[..2], [early, SubscriptionId(4)]);
+    }
+}
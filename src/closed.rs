@@ -0,0 +1,105 @@
+//! Support for `define_events!`, a fixed-array manager for a closed set of event types.
+
+/// Implemented by `define_events!` for each declared event, giving its slot in manager `M`.
+pub trait EventIndex<M> {
+    const INDEX: usize;
+}
+
+#[doc(hidden)]
+pub type ClosedListeners<E> = Vec<Box<dyn FnMut(&E)>>;
+
+/// Defines a manager for a fixed set of event types known up front:
+/// `define_events!(pub GameEvents { PlayerJumped, EnemySpawned });`
+///
+/// The generated manager keeps each type's listeners at a fixed index of an array, so
+/// `dispatch` is an array index instead of a `TypeId` lookup. Subscribing to or dispatching a
+/// type that wasn't declared doesn't compile. It has only `new`, `subscribe` and `dispatch`;
+/// use `EventManager` for everything else.
+#[macro_export]
+macro_rules! define_events {
+    ($vis:vis $manager:ident { $($event:ident),+ $(,)? }) => {
+        $vis struct $manager {
+            listeners: [::std::boxed::Box<dyn ::std::any::Any>; [$(stringify!($event)),+].len()],
+        }
+
+        impl $manager {
+            pub fn new() -> Self {
+                $manager {
+                    // Declaration order, matching the indices below.
+                    listeners: [$(
+                        ::std::boxed::Box::new($crate::closed::ClosedListeners::<$event>::new())
+                            as ::std::boxed::Box<dyn ::std::any::Any>
+                    ),+],
+                }
+            }
+
+            /// Subscribes a listener to the declared event type `E`.
+            pub fn subscribe<E: $crate::closed::EventIndex<Self> + 'static>(&mut self, listener: impl FnMut(&E) + 'static) {
+                self.slot::<E>().push(::std::boxed::Box::new(listener));
+            }
+
+            /// Dispatches an event to all listeners of its type, in subscription order.
+            pub fn dispatch<E: $crate::closed::EventIndex<Self> + 'static>(&mut self, event: &E) {
+                for listener in self.slot::<E>() {
+                    listener(event);
+                }
+            }
+
+            fn slot<E: $crate::closed::EventIndex<Self> + 'static>(&mut self) -> &mut $crate::closed::ClosedListeners<E> {
+                self.listeners[E::INDEX]
+                    .downcast_mut()
+                    .expect("each slot holds the listeners of the event declared at its index")
+            }
+        }
+
+        impl ::std::default::Default for $manager {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        $crate::define_events!(@index $manager, 0usize; $($event),+);
+    };
+    (@index $manager:ident, $index:expr; $head:ident $(, $tail:ident)*) => {
+        impl $crate::closed::EventIndex<$manager> for $head {
+            const INDEX: usize = $index;
+        }
+        $crate::define_events!(@index $manager, $index + 1; $($tail),*);
+    };
+    (@index $manager:ident, $index:expr;) => {};
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    struct KeyPressed(char);
+    struct WindowResized(u32, u32);
+    struct Quit;
+
+    define_events!(AppEvents { KeyPressed, WindowResized, Quit });
+
+    #[test]
+    fn closed_manager_routes_by_declared_index() {
+        let mut events = AppEvents::new();
+        let (tx, rx) = mpsc::channel::<String>();
+
+        let tx_key = tx.clone();
+        events.subscribe(move |event: &KeyPressed| {
+            let _ = tx_key.send(format!("key {}", event.0));
+        });
+        let tx_resize = tx.clone();
+        events.subscribe(move |event: &WindowResized| {
+            let _ = tx_resize.send(format!("resize {}x{}", event.0, event.1));
+        });
+        let tx_quit = tx.clone();
+        events.subscribe(move |_: &Quit| {
+            let _ = tx_quit.send("quit".to_string());
+        });
+
+        events.dispatch(&WindowResized(800, 600));
+        events.dispatch(&KeyPressed('q'));
+        events.dispatch(&Quit);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["resize 800x600", "key q", "quit"]);
+    }
+}
@@ -0,0 +1,480 @@
+use std::any::{Any, TypeId};
+use std::ops::ControlFlow;
+
+use crate::{EventManager, ListenerEntry, SubscriptionId};
+
+impl EventManager {
+    /// Subscribes an emitter listener for event type `E` producing outputs of type `O`.
+    /// On each `collect::<E, O>` the listener may push any number of values (including none)
+    /// into the shared output. Emitters only run from `collect`, never from `dispatch`.
+    pub fn subscribe_emitter<E: Any + 'static, O: Any + 'static>(
+        &mut self,
+        mut listener: impl FnMut(&E, &mut Vec<O>) + 'static,
+    ) -> SubscriptionId {
+        let id = self.allocate_id();
+        self.remember_type::<E>();
+        self.remember_type::<O>();
+        let emitters = self.emitters.entry((TypeId::of::<E>(), TypeId::of::<O>())).or_default();
+
+        // Same trick as `subscribe`: the boxed emitter downcasts both the event and the
+        // output vector back to the concrete types it was registered with.
+        let boxed_emitter = Box::new(move |event: &dyn Any, out: &mut dyn Any| {
+            if let (Some(specific_event), Some(out)) = (event.downcast_ref::<E>(), out.downcast_mut::<Vec<O>>()) {
+                listener(specific_event, out);
+            }
+        });
+
+        emitters.push(ListenerEntry { id, listener: boxed_emitter, retired: None });
+        id
+    }
+
+    /// Subscribes a decision listener for event type `E` that accepts (`true`) or
+    /// rejects (`false`) each event. Verdicts only run from `dispatch_fanout`, `dispatch_veto`
+    /// and `dispatch_any`.
+    pub fn subscribe_verdict<E: Any + 'static>(&mut self, mut listener: impl FnMut(&E) -> bool + 'static) -> SubscriptionId {
+        let id = self.allocate_id();
+        self.remember_type::<E>();
+        let boxed_verdict = Box::new(move |event: &dyn Any| event.downcast_ref::<E>().is_some_and(&mut listener));
+
+        self.verdicts
+            .entry(TypeId::of::<E>())
+            .or_default()
+            .push(ListenerEntry { id, listener: boxed_verdict, retired: None });
+        id
+    }
+
+    /// Asks every verdict listener of `E` about `event` and reports each decision
+    /// alongside the id of the listener that made it, in subscription order.
+    pub fn dispatch_fanout<E: Any + 'static>(&mut self, event: &E) -> Vec<(SubscriptionId, bool)> {
+        let Some(verdicts) = self.verdicts.get_mut(&TypeId::of::<E>()) else {
+            return Vec::new();
+        };
+        verdicts.iter_mut().map(|entry| (entry.id, (entry.listener)(event))).collect()
+    }
+
+    /// Approves `event` only if every verdict listener of `E` accepts it. Listeners are asked
+    /// in subscription order and the first rejection decides: the listeners after it are not
+    /// asked. With no verdict listeners the event is approved.
+    pub fn dispatch_veto<E: Any + 'static>(&mut self, event: &E) -> bool {
+        self.verdicts
+            .get_mut(&TypeId::of::<E>())
+            .is_none_or(|verdicts| verdicts.iter_mut().all(|entry| (entry.listener)(event)))
+    }
+
+    /// Approves `event` if any verdict listener of `E` accepts it. Listeners are asked in
+    /// subscription order and the first acceptance decides: the listeners after it are not
+    /// asked. With no verdict listeners the event is rejected.
+    pub fn dispatch_any<E: Any + 'static>(&mut self, event: &E) -> bool {
+        self.verdicts
+            .get_mut(&TypeId::of::<E>())
+            .is_some_and(|verdicts| verdicts.iter_mut().any(|entry| (entry.listener)(event)))
+    }
+
+    /// Subscribes a query listener for event type `E` that answers each event with an `R`.
+    /// Queries only run from `dispatch_query` and `dispatch_fold`.
+    pub fn subscribe_query<E: Any + 'static, R: Any + 'static>(&mut self, mut listener: impl FnMut(&E) -> R + 'static) -> SubscriptionId {
+        let id = self.allocate_id();
+        self.remember_type::<E>();
+        self.remember_type::<R>();
+        let boxed_query = Box::new(move |event: &dyn Any, slot: &mut dyn Any| {
+            if let (Some(specific_event), Some(slot)) = (event.downcast_ref::<E>(), slot.downcast_mut::<Option<R>>()) {
+                *slot = Some(listener(specific_event));
+            }
+        });
+
+        self.queries
+            .entry((TypeId::of::<E>(), TypeId::of::<R>()))
+            .or_default()
+            .push(ListenerEntry { id, listener: boxed_query, retired: None });
+        id
+    }
+
+    /// Runs every query listener for `(E, R)` and returns their answers in dispatch order.
+    pub fn dispatch_query<E: Any + 'static, R: Any + 'static>(&mut self, event: &E) -> Vec<R> {
+        self.dispatch_fold(event, Vec::new(), |mut results, result| {
+            results.push(result);
+            results
+        })
+    }
+
+    /// Runs every query listener for `(E, R)` and folds their answers into `init` without
+    /// collecting them first. `f` sees the answers in dispatch order.
+    pub fn dispatch_fold<E: Any + 'static, R: Any + 'static, A>(&mut self, event: &E, init: A, f: impl Fn(A, R) -> A) -> A {
+        let Some(queries) = self.queries.get_mut(&(TypeId::of::<E>(), TypeId::of::<R>())) else {
+            return init;
+        };
+        let mut acc = init;
+        let mut slot: Option<R> = None;
+        for entry in queries {
+            (entry.listener)(event, &mut slot);
+            if let Some(result) = slot.take() {
+                acc = f(acc, result);
+            }
+        }
+        acc
+    }
+
+    /// Subscribes an accumulating listener for event type `E` that adds to a shared `A` and
+    /// may halt the rest of the listeners with `ControlFlow::Break`, e.g. once `A` holds
+    /// enough. Accumulators only run from `dispatch_accumulate`.
+    pub fn subscribe_accumulating<E: Any + 'static, A: Any + 'static>(
+        &mut self,
+        mut listener: impl FnMut(&E, &mut A) -> ControlFlow<()> + 'static,
+    ) -> SubscriptionId {
+        let id = self.allocate_id();
+        self.remember_type::<E>();
+        self.remember_type::<A>();
+        let boxed_accumulator = Box::new(move |event: &dyn Any, acc: &mut dyn Any| {
+            match (event.downcast_ref::<E>(), acc.downcast_mut::<A>()) {
+                (Some(specific_event), Some(acc)) => listener(specific_event, acc).is_continue(),
+                _ => true,
+            }
+        });
+
+        self.accumulators
+            .entry((TypeId::of::<E>(), TypeId::of::<A>()))
+            .or_default()
+            .push(ListenerEntry { id, listener: boxed_accumulator, retired: None });
+        id
+    }
+
+    /// Runs the accumulating listeners for `(E, A)` in subscription order, each adding to the
+    /// accumulator that starts as `init`, until one returns `ControlFlow::Break` or all have
+    /// run. Returns the final accumulator.
+    pub fn dispatch_accumulate<E: Any + 'static, A: Any + 'static>(&mut self, event: &E, init: A) -> A {
+        let mut acc = init;
+        if let Some(accumulators) = self.accumulators.get_mut(&(TypeId::of::<E>(), TypeId::of::<A>())) {
+            for entry in accumulators {
+                if !(entry.listener)(event, &mut acc) {
+                    break;
+                }
+            }
+        }
+        acc
+    }
+
+    /// Runs every emitter registered for `(E, O)` and returns everything they pushed,
+    /// in subscription order.
+    pub fn collect<E: Any + 'static, O: Any + 'static>(&mut self, event: &E) -> Vec<O> {
+        let mut out: Vec<O> = Vec::new();
+        self.dispatch_into(event, &mut out);
+        out
+    }
+
+    /// Like `collect`, but the emitters push into `out`, a buffer the caller owns and can
+    /// reuse across frames to avoid allocating a new `Vec` per call. Emitters append after
+    /// whatever `out` already holds; clearing it between calls is up to the caller.
+    // The emitters downcast `out` back to a `Vec<O>` to push into it, so a slice won't do.
+    #[allow(clippy::ptr_arg)]
+    pub fn dispatch_into<E: Any + 'static, O: Any + 'static>(&mut self, event: &E, out: &mut Vec<O>) {
+        if let Some(emitters) = self.emitters.get_mut(&(TypeId::of::<E>(), TypeId::of::<O>())) {
+            for entry in emitters {
+                (entry.listener)(event, out);
+            }
+        }
+    }
+
+    /// Subscribes a rewriting listener for event type `E` that may modify or wholesale
+    /// replace the event before the next rewriter sees it. Rewriters only run from
+    /// `dispatch_pipeline`, never from `dispatch`.
+    pub fn subscribe_rewriting<E: Any + 'static>(&mut self, mut listener: impl FnMut(&mut E) + 'static) -> SubscriptionId {
+        let id = self.allocate_id();
+        self.remember_type::<E>();
+        let boxed_rewriter = Box::new(move |event: &mut dyn Any| {
+            if let Some(specific_event) = event.downcast_mut::<E>() {
+                listener(specific_event);
+            }
+        });
+
+        self.rewriters
+            .entry(TypeId::of::<E>())
+            .or_default()
+            .push(ListenerEntry { id, listener: boxed_rewriter, retired: None });
+        id
+    }
+
+    /// Threads `event` through every rewriting listener of `E` in subscription order and
+    /// returns the final value, e.g. raw input -> remapped -> filtered. With no rewriters
+    /// the event comes back unchanged. Regular listeners are not involved; dispatch the
+    /// result if they should see it.
+    pub fn dispatch_pipeline<E: Any + 'static>(&mut self, mut event: E) -> E {
+        if let Some(rewriters) = self.rewriters.get_mut(&TypeId::of::<E>()) {
+            for entry in rewriters {
+                (entry.listener)(&mut event);
+            }
+        }
+        event
+    }
+}
+
+/// An event offered to `subscribe_consuming` listeners, which may take ownership of it.
+pub struct Consumable<'a, E> {
+    slot: &'a mut Option<E>,
+}
+
+impl<E> Consumable<'_, E> {
+    /// Looks at the event without taking it.
+    pub fn peek(&self) -> Option<&E> {
+        self.slot.as_ref()
+    }
+
+    /// Takes ownership of the event. Returns `None` if an earlier listener took it already.
+    pub fn take(&mut self) -> Option<E> {
+        self.slot.take()
+    }
+}
+
+impl EventManager {
+    /// Subscribes a consuming listener for event type `E`, which is offered the event and
+    /// may take ownership of it instead of only borrowing it. Consumers only run from
+    /// `dispatch_consume`.
+    pub fn subscribe_consuming<E: Any + 'static>(&mut self, mut listener: impl FnMut(&mut Consumable<E>) + 'static) -> SubscriptionId {
+        let id = self.allocate_id();
+        self.remember_type::<E>();
+        let boxed_consumer = Box::new(move |slot: &mut dyn Any| {
+            if let Some(slot) = slot.downcast_mut::<Option<E>>() {
+                listener(&mut Consumable { slot });
+            }
+        });
+
+        self.consumers
+            .entry(TypeId::of::<E>())
+            .or_default()
+            .push(ListenerEntry { id, listener: boxed_consumer, retired: None });
+        id
+    }
+
+    /// Offers `event` to the consuming listeners of `E` in subscription order, without
+    /// cloning it. The first listener to `take` it becomes its owner and the remaining
+    /// consumers are not called, so an event is consumed at most once. Returns `None` if it
+    /// was consumed, or gives the event back if nobody took it.
+    pub fn dispatch_consume<E: Any + 'static>(&mut self, event: E) -> Option<E> {
+        let mut slot = Some(event);
+        if let Some(consumers) = self.consumers.get_mut(&TypeId::of::<E>()) {
+            for entry in consumers {
+                (entry.listener)(&mut slot);
+                if slot.is_none() {
+                    break;
+                }
+            }
+        }
+        slot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    struct BuildMenu {
+        in_game: bool,
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct MenuItem(&'static str);
+
+    #[test]
+    fn collect_gathers_outputs_from_all_emitters() {
+        let mut event_manager = EventManager::new();
+
+        event_manager.subscribe_emitter(|_: &BuildMenu, items: &mut Vec<MenuItem>| {
+            items.push(MenuItem("Options"));
+            items.push(MenuItem("Quit"));
+        });
+        event_manager.subscribe_emitter(|event: &BuildMenu, items: &mut Vec<MenuItem>| {
+            if event.in_game {
+                items.push(MenuItem("Save"));
+            }
+        });
+
+        let items: Vec<MenuItem> = event_manager.collect(&BuildMenu { in_game: false });
+        assert_eq!(items, vec![MenuItem("Options"), MenuItem("Quit")]);
+
+        let items: Vec<MenuItem> = event_manager.collect(&BuildMenu { in_game: true });
+        assert_eq!(items, vec![MenuItem("Options"), MenuItem("Quit"), MenuItem("Save")]);
+
+        // Emitters for a different output type are not involved.
+        let names: Vec<String> = event_manager.collect(&BuildMenu { in_game: true });
+        assert!(names.is_empty());
+    }
+
+    #[test]
+    fn dispatch_into_appends_to_the_callers_buffer() {
+        let mut event_manager = EventManager::new();
+        event_manager.subscribe_emitter(|event: &BuildMenu, items: &mut Vec<MenuItem>| {
+            if event.in_game {
+                items.push(MenuItem("Save"));
+            }
+        });
+
+        let mut items = vec![MenuItem("Resume")];
+        event_manager.dispatch_into(&BuildMenu { in_game: true }, &mut items);
+        assert_eq!(items, vec![MenuItem("Resume"), MenuItem("Save")]);
+
+        items.clear();
+        let capacity = items.capacity();
+        event_manager.dispatch_into(&BuildMenu { in_game: true }, &mut items);
+        assert_eq!(items, vec![MenuItem("Save")]);
+        assert_eq!(items.capacity(), capacity);
+    }
+
+    struct ResolveAttack;
+
+    #[test]
+    fn accumulate_stops_once_a_listener_breaks() {
+        let mut event_manager = EventManager::new();
+        event_manager.subscribe_accumulating(|_: &ResolveAttack, damage: &mut Vec<u32>| {
+            damage.push(5);
+            ControlFlow::Continue(())
+        });
+        event_manager.subscribe_accumulating(|_: &ResolveAttack, damage: &mut Vec<u32>| {
+            damage.push(20);
+            if damage.iter().sum::<u32>() >= 25 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        event_manager.subscribe_accumulating(|_: &ResolveAttack, damage: &mut Vec<u32>| {
+            damage.push(100);
+            ControlFlow::Continue(())
+        });
+
+        assert_eq!(event_manager.dispatch_accumulate(&ResolveAttack, Vec::<u32>::new()), vec![5, 20]);
+        assert_eq!(event_manager.dispatch_accumulate(&ResolveAttack, vec![0u32]), vec![0, 5, 20]);
+        // Accumulators of a different type aren't involved.
+        assert_eq!(event_manager.dispatch_accumulate(&ResolveAttack, 7u32), 7);
+    }
+
+    struct UseItem {
+        item: &'static str,
+    }
+
+    #[test]
+    fn dispatch_fanout_maps_decisions_to_listeners() {
+        let mut event_manager = EventManager::new();
+
+        let inventory = event_manager.subscribe_verdict(|_: &UseItem| true);
+        let cooldown = event_manager.subscribe_verdict(|event: &UseItem| event.item != "potion");
+
+        assert_eq!(
+            event_manager.dispatch_fanout(&UseItem { item: "potion" }),
+            vec![(inventory, true), (cooldown, false)]
+        );
+        assert_eq!(
+            event_manager.dispatch_fanout(&UseItem { item: "sword" }),
+            vec![(inventory, true), (cooldown, true)]
+        );
+        assert!(event_manager.dispatch_fanout(&BuildMenu { in_game: true }).is_empty());
+    }
+
+    #[test]
+    fn veto_and_any_short_circuit() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<&'static str>();
+        assert!(event_manager.dispatch_veto(&UseItem { item: "potion" }));
+        assert!(!event_manager.dispatch_any(&UseItem { item: "potion" }));
+
+        let tx_alive = tx.clone();
+        event_manager.subscribe_verdict(move |event: &UseItem| {
+            let _ = tx_alive.send("alive");
+            event.item != "revive"
+        });
+        let tx_owned = tx.clone();
+        event_manager.subscribe_verdict(move |_: &UseItem| {
+            let _ = tx_owned.send("owned");
+            true
+        });
+
+        assert!(event_manager.dispatch_veto(&UseItem { item: "potion" }));
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["alive", "owned"]);
+
+        assert!(!event_manager.dispatch_veto(&UseItem { item: "revive" }));
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["alive"], "Decided by the first rejection");
+
+        assert!(event_manager.dispatch_any(&UseItem { item: "potion" }));
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["alive"], "Decided by the first acceptance");
+    }
+
+    struct Hit {
+        base: u32,
+    }
+
+    #[test]
+    fn dispatch_fold_reduces_query_results_in_order() {
+        let mut event_manager = EventManager::new();
+
+        event_manager.subscribe_query(|event: &Hit| event.base);
+        event_manager.subscribe_query(|_: &Hit| 5u32);
+        event_manager.subscribe_query(|event: &Hit| event.base * 2);
+
+        let hit = Hit { base: 10 };
+        assert_eq!(event_manager.dispatch_query::<Hit, u32>(&hit), vec![10, 5, 20]);
+        assert_eq!(event_manager.dispatch_fold(&hit, 0u32, |total, damage: u32| total + damage), 35);
+        assert_eq!(event_manager.dispatch_fold(&hit, 0u32, |best, damage: u32| best.max(damage)), 20);
+
+        let order = event_manager.dispatch_fold(&hit, String::new(), |order, damage: u32| format!("{order}{damage},"));
+        assert_eq!(order, "10,5,20,");
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Input {
+        Key(char),
+        Action(&'static str),
+    }
+
+    #[test]
+    fn dispatch_pipeline_threads_the_event_through_rewriters() {
+        let mut event_manager = EventManager::new();
+        event_manager.subscribe_rewriting(|input: &mut Input| {
+            if *input == Input::Key('w') {
+                *input = Input::Action("move forward");
+            }
+        });
+        event_manager.subscribe_rewriting(|input: &mut Input| {
+            if let Input::Action(action) = input {
+                *action = if *action == "move forward" { "sprint" } else { action };
+            }
+        });
+
+        assert_eq!(event_manager.dispatch_pipeline(Input::Key('w')), Input::Action("sprint"));
+        assert_eq!(event_manager.dispatch_pipeline(Input::Key('q')), Input::Key('q'));
+        assert_eq!(event_manager.dispatch_pipeline(7u32), 7);
+    }
+
+    struct FileUpload {
+        name: &'static str,
+        bytes: Vec<u8>,
+    }
+
+    #[test]
+    fn dispatch_consume_hands_ownership_to_the_first_taker() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<String>();
+
+        let tx_size = tx.clone();
+        event_manager.subscribe_consuming(move |upload: &mut Consumable<FileUpload>| {
+            if let Some(upload) = upload.peek() {
+                let _ = tx_size.send(format!("{} is {} bytes", upload.name, upload.bytes.len()));
+            }
+        });
+        let tx_store = tx.clone();
+        event_manager.subscribe_consuming(move |upload: &mut Consumable<FileUpload>| {
+            if upload.peek().is_some_and(|upload| upload.name.ends_with(".png")) {
+                let upload = upload.take().unwrap();
+                let _ = tx_store.send(format!("stored {}", upload.name));
+            }
+        });
+        event_manager.subscribe_consuming(|_: &mut Consumable<FileUpload>| panic!("already consumed"));
+
+        let png = FileUpload { name: "a.png", bytes: vec![0; 3] };
+        assert!(event_manager.dispatch_consume(png).is_none());
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["a.png is 3 bytes", "stored a.png"]);
+
+        let mut other = EventManager::new();
+        let txt = FileUpload { name: "b.txt", bytes: vec![1] };
+        let returned = other.dispatch_consume(txt).expect("nobody consumed it");
+        assert_eq!(returned.bytes, vec![1]);
+    }
+}
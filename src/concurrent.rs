@@ -0,0 +1,307 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::thread;
+
+use crate::SubscriptionId;
+
+type SendListener = Box<dyn FnMut(&dyn Any) + Send>;
+// Each listener has its own lock, so dispatches on different threads only wait for each
+// other when they reach the same listener.
+type SyncListener = Arc<Mutex<SendListener>>;
+// Each type's listeners sit behind their own lock, so subscribing to or dispatching one type
+// doesn't wait for another type. The outer lock is only written to add or drop a type.
+type Bucket = Arc<RwLock<Vec<(SubscriptionId, SyncListener)>>>;
+type ListenerMap = HashMap<TypeId, Bucket>;
+
+/// An event manager that can be shared between threads, e.g. behind an `Arc`, with
+/// `subscribe`, `unsubscribe` and `dispatch` all taking `&self`. Listeners must be `Send`.
+///
+/// Each event type's listeners are locked separately, so threads working with different
+/// types don't contend. `dispatch` runs the listeners on the calling thread, in
+/// subscription order, over a snapshot taken when it starts: listeners subscribed meanwhile wait for the next dispatch,
+/// and unsubscribed ones may still receive the current one. Listeners may subscribe,
+/// unsubscribe and dispatch other events themselves, but a listener that ends up
+/// dispatching to itself deadlocks. It has none of `EventManager`'s other features.
+pub struct SharedEventManager {
+    listeners: RwLock<ListenerMap>,
+    next_id: AtomicU64,
+}
+
+impl Default for SharedEventManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SharedEventManager {
+    pub fn new() -> Self {
+        SharedEventManager {
+            listeners: RwLock::new(HashMap::new()),
+            next_id: AtomicU64::new(0),
+        }
+    }
+
+    /// Subscribes a listener closure to the event type `E`.
+    pub fn subscribe<E: Any + 'static>(&self, mut listener: impl FnMut(&E) + Send + 'static) -> SubscriptionId {
+        let id = SubscriptionId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        let boxed_listener: SendListener = Box::new(move |event: &dyn Any| {
+            if let Some(specific_event) = event.downcast_ref::<E>() {
+                listener(specific_event);
+            }
+        });
+        let entry = (id, Arc::new(Mutex::new(boxed_listener)));
+        let type_id = TypeId::of::<E>();
+        // Only a type's first listener needs the outer lock for writing.
+        let existing = self.read().get(&type_id).map(Arc::clone);
+        let bucket = existing.unwrap_or_else(|| Arc::clone(self.write().entry(type_id).or_default()));
+        lock_write(&bucket).push(entry);
+
+        // An `unsubscribe` of the type's last listener may have dropped the bucket before the
+        // push landed. Put it back, or move the entry over if another thread already made a
+        // new one. Once the push is in, `unsubscribe` no longer sees the bucket as empty.
+        let still_mapped = self.read().get(&type_id).is_some_and(|current| Arc::ptr_eq(current, &bucket));
+        if !still_mapped {
+            let mut listeners = self.write();
+            let current = listeners.entry(type_id).or_insert_with(|| Arc::clone(&bucket));
+            if !Arc::ptr_eq(current, &bucket) {
+                let orphaned = std::mem::take(&mut *lock_write(&bucket));
+                lock_write(current).extend(orphaned);
+            }
+        }
+        id
+    }
+
+    /// Removes the listener `id`. Returns false if no such listener exists.
+    pub fn unsubscribe(&self, id: SubscriptionId) -> bool {
+        let buckets: Vec<(TypeId, Bucket)> = self.read().iter().map(|(type_id, bucket)| (*type_id, Arc::clone(bucket))).collect();
+        for (type_id, bucket) in buckets {
+            let mut entries = lock_write(&bucket);
+            let Some(index) = entries.iter().position(|(entry_id, _)| *entry_id == id) else {
+                continue;
+            };
+            entries.remove(index);
+            let emptied = entries.is_empty();
+            drop(entries);
+            if emptied {
+                let mut listeners = self.write();
+                // Another thread may have subscribed in between; only drop a bucket that is still empty.
+                if listeners.get(&type_id).is_some_and(|bucket| lock_read(bucket).is_empty()) {
+                    listeners.remove(&type_id);
+                }
+            }
+            return true;
+        }
+        false
+    }
+
+    /// Dispatches `event` to all listeners of `E`. Returns how many listeners were invoked.
+    pub fn dispatch<E: Any + 'static>(&self, event: &E) -> usize {
+        let Some(bucket) = self.read().get(&TypeId::of::<E>()).map(Arc::clone) else {
+            return 0;
+        };
+        let snapshot: Vec<SyncListener> = lock_read(&bucket).iter().map(|(_, listener)| Arc::clone(listener)).collect();
+        for listener in &snapshot {
+            // A listener that panicked can't have left the manager inconsistent; keep calling it.
+            let mut listener = listener.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            listener(event);
+        }
+        snapshot.len()
+    }
+
+    /// Subscribes a listener to `Arc<Mutex<E>>` payloads that several listeners mutate
+    /// together, see `dispatch_shared_mut`. Each call gets its own handle to the payload.
+    pub fn subscribe_shared_mut<E: Any + Send>(&self, listener: impl Fn(Arc<Mutex<E>>) + Send + Sync + 'static) -> SubscriptionId {
+        self.subscribe(move |event: &Arc<Mutex<E>>| listener(Arc::clone(event)))
+    }
+
+    /// Dispatches `event` to every listener of `Arc<Mutex<E>>`, each on its own thread, and
+    /// returns once all of them have finished, e.g. for several systems aggregating into
+    /// one result in parallel. Listeners lock the payload while they mutate it, so keep the
+    /// guard short. A listener that dispatches again while holding the guard deadlocks, since
+    /// the listeners of that dispatch wait for the guard while it waits for them: drop the
+    /// guard first. A panicking listener's panic is resumed here once the others are done.
+    /// Plain `dispatch` of the same `Arc` runs the same listeners one after another instead.
+    /// Returns how many listeners were invoked.
+    pub fn dispatch_shared_mut<E: Any + Send>(&self, event: Arc<Mutex<E>>) -> usize {
+        let Some(bucket) = self.read().get(&TypeId::of::<Arc<Mutex<E>>>()).map(Arc::clone) else {
+            return 0;
+        };
+        let snapshot: Vec<SyncListener> = lock_read(&bucket).iter().map(|(_, listener)| Arc::clone(listener)).collect();
+        let event = &event;
+        thread::scope(|scope| {
+            for listener in &snapshot {
+                scope.spawn(move || {
+                    let mut listener = listener.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+                    listener(event);
+                });
+            }
+        });
+        snapshot.len()
+    }
+
+    /// Returns how many listeners `E` has.
+    pub fn listener_count<E: Any + 'static>(&self) -> usize {
+        self.read().get(&TypeId::of::<E>()).map_or(0, |bucket| lock_read(bucket).len())
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, ListenerMap> {
+        lock_read(&self.listeners)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, ListenerMap> {
+        lock_write(&self.listeners)
+    }
+}
+
+// The map and buckets are only ever changed by single inserts and removals, which can't
+// panic halfway, so a poisoned lock still guards consistent data.
+fn lock_read<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn lock_write<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::mpsc;
+    use std::thread::ThreadId;
+    use std::time::Duration;
+
+    struct ChunkLoaded(u64);
+
+    #[test]
+    fn threads_subscribe_and_dispatch_concurrently() {
+        let event_manager = Arc::new(SharedEventManager::new());
+        let total = Arc::new(AtomicUsize::new(0));
+
+        let subscribers: Vec<_> = (0..4)
+            .map(|_| {
+                let event_manager = Arc::clone(&event_manager);
+                let total = Arc::clone(&total);
+                thread::spawn(move || {
+                    event_manager.subscribe(move |event: &ChunkLoaded| {
+                        total.fetch_add(event.0 as usize, Ordering::Relaxed);
+                    })
+                })
+            })
+            .collect();
+        let ids: Vec<SubscriptionId> = subscribers.into_iter().map(|handle| handle.join().unwrap()).collect();
+        assert_eq!(event_manager.listener_count::<ChunkLoaded>(), 4);
+
+        let dispatchers: Vec<_> = (0..8)
+            .map(|_| {
+                let event_manager = Arc::clone(&event_manager);
+                thread::spawn(move || event_manager.dispatch(&ChunkLoaded(1)))
+            })
+            .collect();
+        for handle in dispatchers {
+            assert_eq!(handle.join().unwrap(), 4);
+        }
+        assert_eq!(total.load(Ordering::Relaxed), 32);
+
+        assert!(event_manager.unsubscribe(ids[0]));
+        assert!(!event_manager.unsubscribe(ids[0]));
+        assert_eq!(event_manager.dispatch(&ChunkLoaded(1)), 3);
+    }
+
+    #[test]
+    fn listeners_can_subscribe_during_dispatch() {
+        let event_manager = Arc::new(SharedEventManager::new());
+        let inner = Arc::clone(&event_manager);
+        event_manager.subscribe(move |_: &ChunkLoaded| {
+            inner.subscribe(|_: &ChunkLoaded| {});
+        });
+
+        assert_eq!(event_manager.dispatch(&ChunkLoaded(0)), 1);
+        assert_eq!(event_manager.dispatch(&ChunkLoaded(0)), 2);
+    }
+
+    #[test]
+    fn other_types_stay_available_during_a_dispatch() {
+        struct EnemySpawned;
+
+        let event_manager = Arc::new(SharedEventManager::new());
+        let (started_tx, started_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let release_rx = Mutex::new(release_rx);
+        event_manager.subscribe(move |_: &ChunkLoaded| {
+            let _ = started_tx.send(());
+            // Held up until the other thread is done, or for a generous timeout.
+            let _ = release_rx.lock().unwrap().recv_timeout(Duration::from_secs(10));
+        });
+
+        let slow = {
+            let event_manager = Arc::clone(&event_manager);
+            thread::spawn(move || event_manager.dispatch(&ChunkLoaded(0)))
+        };
+        started_rx.recv().unwrap();
+
+        // While `ChunkLoaded` is mid-dispatch, another type can change and dispatch freely,
+        // and so can `ChunkLoaded`'s own listener list.
+        let spawned = event_manager.subscribe(|_: &EnemySpawned| {});
+        assert_eq!(event_manager.dispatch(&EnemySpawned), 1);
+        assert!(event_manager.unsubscribe(spawned));
+        assert_eq!(event_manager.listener_count::<EnemySpawned>(), 0);
+        event_manager.subscribe(|_: &ChunkLoaded| {});
+        assert_eq!(event_manager.listener_count::<ChunkLoaded>(), 2);
+
+        release_tx.send(()).unwrap();
+        assert_eq!(slow.join().unwrap(), 1);
+    }
+
+    #[test]
+    fn subscriptions_survive_the_last_listener_leaving_concurrently() {
+        // Both threads keep taking `ChunkLoaded` from one listener to none, so each
+        // subscribe races the other thread's removal of the emptied bucket.
+        let event_manager = Arc::new(SharedEventManager::new());
+        let churners: Vec<_> = (0..2)
+            .map(|_| {
+                let event_manager = Arc::clone(&event_manager);
+                thread::spawn(move || {
+                    for _ in 0..5_000 {
+                        let id = event_manager.subscribe(|_: &ChunkLoaded| {});
+                        assert!(event_manager.unsubscribe(id), "the subscription was lost");
+                    }
+                })
+            })
+            .collect();
+        for churner in churners {
+            churner.join().unwrap();
+        }
+        assert_eq!(event_manager.listener_count::<ChunkLoaded>(), 0);
+    }
+
+    #[test]
+    fn shared_mut_listeners_aggregate_in_parallel() {
+        #[derive(Default)]
+        struct Tally {
+            score: u32,
+            threads: Vec<ThreadId>,
+        }
+
+        let event_manager = SharedEventManager::new();
+        for points in 1..=4 {
+            event_manager.subscribe_shared_mut(move |tally: Arc<Mutex<Tally>>| {
+                let mut tally = tally.lock().unwrap();
+                tally.score += points;
+                tally.threads.push(thread::current().id());
+            });
+        }
+
+        let tally = Arc::new(Mutex::new(Tally::default()));
+        assert_eq!(event_manager.dispatch_shared_mut(Arc::clone(&tally)), 4);
+        let tally = tally.lock().unwrap();
+        assert_eq!(tally.score, 10);
+        let mut threads = tally.threads.clone();
+        threads.sort_by_key(|id| format!("{id:?}"));
+        threads.dedup();
+        assert_eq!(threads.len(), 4, "every listener ran on its own thread");
+        assert!(!threads.contains(&thread::current().id()));
+    }
+}
@@ -0,0 +1,204 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+
+use crate::{EventManager, ListenerEntry, SubscriptionId};
+
+/// The id passed to `subscribe_with_id` already belongs to a listener of this manager.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdInUse(pub SubscriptionId);
+
+impl fmt::Display for IdInUse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} is already in use", self.0)
+    }
+}
+
+impl std::error::Error for IdInUse {}
+
+/// Why `try_subscribe` couldn't subscribe a listener. There is no listener cap to exceed,
+/// and a frozen manager has no way to subscribe at all, so a duplicate key is the only case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubscribeError {
+    /// The key is already the id of a listener of this manager, see `subscribe_with_id`.
+    DuplicateKey(SubscriptionId),
+}
+
+impl fmt::Display for SubscribeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubscribeError::DuplicateKey(id) => write!(f, "{id:?} is already in use"),
+        }
+    }
+}
+
+impl std::error::Error for SubscribeError {}
+
+impl From<IdInUse> for SubscribeError {
+    fn from(IdInUse(id): IdInUse) -> Self {
+        SubscribeError::DuplicateKey(id)
+    }
+}
+
+impl EventManager {
+    /// Subscribes a listener to `E` like `subscribe`, under the caller's own numeric `id`,
+    /// e.g. an entity id, so `unsubscribe` can be driven from it without a mapping table.
+    /// Ids are unique across the whole manager, since `unsubscribe` takes nothing else: an
+    /// id held by any listener is rejected. Ids handed out afterwards by the other
+    /// `subscribe` methods are greater than every id chosen this way, unless that leaves no
+    /// greater ones; they then start again from the lowest ids not in use. Choosing an id
+    /// doesn't affect ordering: the listener runs after those subscribed before it.
+    pub fn subscribe_with_id<E: Any + 'static>(&mut self, id: u64, mut listener: impl FnMut(&E) + 'static) -> Result<SubscriptionId, IdInUse> {
+        let id = SubscriptionId(id);
+        if self.contains_id(id) {
+            return Err(IdInUse(id));
+        }
+        if !self.ids_wrapped && id.0 >= self.next_id {
+            self.advance_next_id(id.0);
+        }
+
+        let boxed_listener = Box::new(move |event: &dyn Any| {
+            if let Some(specific_event) = event.downcast_ref::<E>() {
+                listener(specific_event);
+            }
+        });
+        self.remember_type::<E>();
+        self.insert_listener(TypeId::of::<E>(), ListenerEntry { id, listener: boxed_listener, retired: None });
+        Ok(id)
+    }
+
+    /// Subscribes a listener to `E` under `key` like `subscribe_with_id`, or under a fresh id
+    /// like `subscribe` when `key` is `None`, e.g. for listeners registered from config that
+    /// may or may not name their own id. Only a key can make it fail.
+    pub fn try_subscribe<E: Any + 'static>(&mut self, key: Option<u64>, listener: impl FnMut(&E) + 'static) -> Result<SubscriptionId, SubscribeError> {
+        match key {
+            Some(key) => Ok(self.subscribe_with_id(key, listener)?),
+            None => Ok(self.subscribe(listener)),
+        }
+    }
+
+    // Whether any listener of any kind holds `id`.
+    pub(crate) fn contains_id(&self, id: SubscriptionId) -> bool {
+        let found = self.listeners.iter().any(|(_, bucket)| bucket.contains_live(id))
+            || pool_contains(&self.shared_listeners, id)
+            || pool_contains(&self.verdicts, id)
+            || pool_contains(&self.queries, id)
+            || pool_contains(&self.accumulators, id)
+            || pool_contains(&self.rewriters, id)
+            || pool_contains(&self.consumers, id)
+            || pool_contains(&self.emitters, id);
+        #[cfg(any(test, feature = "async"))]
+        let found = found || pool_contains(&self.async_listeners, id) || pool_contains(&self.async_queries, id);
+        found
+    }
+
+    // The ids of every listener of any kind, retired ones included.
+    pub(crate) fn entry_ids(&self) -> Vec<SubscriptionId> {
+        let mut ids: Vec<SubscriptionId> = self.listeners.iter().flat_map(|(_, bucket)| bucket.ids()).collect();
+        ids.extend(pool_ids(&self.shared_listeners));
+        ids.extend(pool_ids(&self.verdicts));
+        ids.extend(pool_ids(&self.queries));
+        ids.extend(pool_ids(&self.accumulators));
+        ids.extend(pool_ids(&self.rewriters));
+        ids.extend(pool_ids(&self.consumers));
+        ids.extend(pool_ids(&self.emitters));
+        #[cfg(any(test, feature = "async"))]
+        ids.extend(pool_ids(&self.async_listeners));
+        #[cfg(any(test, feature = "async"))]
+        ids.extend(pool_ids(&self.async_queries));
+        ids
+    }
+}
+
+fn pool_contains<K: Eq + Hash, L>(pool: &HashMap<K, Vec<ListenerEntry<L>>>, id: SubscriptionId) -> bool {
+    pool.values().flatten().any(|entry| entry.id == id)
+}
+
+fn pool_ids<K, L>(pool: &HashMap<K, Vec<ListenerEntry<L>>>) -> impl Iterator<Item = SubscriptionId> + '_ {
+    pool.values().flatten().map(|entry| entry.id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::mpsc;
+
+    struct EntityDamaged(u32);
+
+    #[test]
+    fn chosen_ids_are_unique_and_unsubscribable() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+
+        let entity = 42;
+        let tx_entity = tx.clone();
+        let id = event_manager
+            .subscribe_with_id(entity, move |event: &EntityDamaged| {
+                let _ = tx_entity.send(event.0);
+            })
+            .unwrap();
+        assert_eq!(event_manager.subscribe_with_id(entity, |_: &u32| {}), Err(IdInUse(id)));
+
+        // Automatically allocated ids never collide with chosen ones.
+        let other = event_manager.subscribe(|_: &EntityDamaged| {});
+        assert!(other > id);
+
+        event_manager.dispatch(&EntityDamaged(5));
+        assert!(event_manager.unsubscribe(id));
+        event_manager.dispatch(&EntityDamaged(6));
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![5]);
+        assert!(event_manager.subscribe_with_id(entity, |_: &EntityDamaged| {}).is_ok());
+    }
+
+    #[test]
+    fn try_subscribe_rejects_duplicate_keys() {
+        let mut event_manager = EventManager::new();
+        let keyed = event_manager.try_subscribe(Some(7), |_: &EntityDamaged| {}).unwrap();
+        assert_eq!(keyed, SubscriptionId(7));
+        assert_eq!(event_manager.try_subscribe(Some(7), |_: &u32| {}), Err(SubscribeError::DuplicateKey(keyed)));
+
+        let unkeyed = event_manager.try_subscribe(None, |_: &EntityDamaged| {}).unwrap();
+        assert!(unkeyed > keyed);
+        assert_eq!(event_manager.listener_count::<EntityDamaged>(), 2);
+        assert_eq!(event_manager.listener_count::<u32>(), 0);
+    }
+
+    #[test]
+    fn ids_wrap_round_past_the_end_of_the_range() {
+        let mut event_manager = EventManager::new();
+        let first = event_manager.subscribe(|_: &EntityDamaged| {});
+        let last = event_manager.subscribe_with_id(u64::MAX, |_: &EntityDamaged| {}).unwrap();
+
+        // Nothing is left above `u64::MAX`, so allocation carries on from the lowest free id.
+        let next = event_manager.subscribe(|_: &EntityDamaged| {});
+        assert_eq!(next, SubscriptionId(1));
+        assert!(next != first && next != last);
+        let chosen = event_manager.subscribe_with_id(2, |_: &EntityDamaged| {}).unwrap();
+        assert_eq!(event_manager.subscribe(|_: &EntityDamaged| {}), SubscriptionId(3));
+        assert!(event_manager.unsubscribe(chosen) && event_manager.unsubscribe(last));
+    }
+
+    #[test]
+    fn chosen_ids_keep_subscription_order() {
+        let mut event_manager = EventManager::new();
+        let order = Rc::new(RefCell::new(Vec::new()));
+        let listener = |name: &'static str| {
+            let order = Rc::clone(&order);
+            move |_: &EntityDamaged| order.borrow_mut().push(name)
+        };
+
+        event_manager.subscribe(listener("first"));
+        let second = event_manager.subscribe(listener("second"));
+        event_manager.subscribe(listener("third"));
+        assert!(event_manager.unsubscribe(second));
+        event_manager.subscribe_with_id(second.0, listener("reused")).unwrap();
+        // Re-sorting by priority must not pull the reused id back into its old place.
+        event_manager.subscribe_with_priority(0, listener("prioritised"));
+
+        event_manager.dispatch(&EntityDamaged(1));
+        assert_eq!(*order.borrow(), vec!["first", "third", "reused", "prioritised"]);
+    }
+}
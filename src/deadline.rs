@@ -0,0 +1,99 @@
+use std::any::{Any, TypeId};
+use std::time::Instant;
+
+use crate::{EventManager, Step};
+
+/// What happened during a `dispatch_with_deadline`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DispatchOutcome {
+    /// How many listeners ran.
+    pub invoked: usize,
+    /// Whether the deadline passed with listeners still waiting to run.
+    pub cut_short: bool,
+}
+
+impl EventManager {
+    /// Dispatches `event` like `dispatch`, but checks the clock before each listener and
+    /// stops once `Instant::now() >= deadline`; the remaining listeners, including those of
+    /// aliased types, don't see the event. A listener is never interrupted: a slow one
+    /// overruns the deadline and only the listeners after it are skipped.
+    pub fn dispatch_with_deadline<E: Any + 'static>(&mut self, event: &E, deadline: Instant) -> DispatchOutcome {
+        let mut cut_short = false;
+        let mut check = |_, _| {
+            if Instant::now() >= deadline {
+                cut_short = true;
+                Step::Stop
+            } else {
+                Step::Run
+            }
+        };
+        let mut invoked = self.dispatch_selected(TypeId::of::<E>(), std::any::type_name::<E>(), event, Some(&mut check));
+        if !self.aliases.is_empty() {
+            invoked += self.dispatch_aliases(TypeId::of::<E>(), event, Some(&mut check));
+        }
+        self.flush_chained();
+        DispatchOutcome { invoked, cut_short }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    struct FrameStarted;
+
+    #[test]
+    fn deadline_stops_between_listeners() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<&'static str>();
+        // Far enough off that the first listener always starts in time; it then overruns
+        // the deadline on purpose.
+        let deadline = Instant::now() + Duration::from_millis(500);
+
+        let tx_slow = tx.clone();
+        event_manager.subscribe(move |_: &FrameStarted| {
+            thread::sleep(deadline.saturating_duration_since(Instant::now()));
+            let _ = tx_slow.send("pathfinding");
+        });
+        let tx_fx = tx.clone();
+        event_manager.subscribe(move |_: &FrameStarted| {
+            let _ = tx_fx.send("particles");
+        });
+        let tx_hud = tx.clone();
+        event_manager.subscribe_fn(move |_: &FrameStarted| {
+            let _ = tx_hud.send("hud");
+        });
+
+        let outcome = event_manager.dispatch_with_deadline(&FrameStarted, deadline);
+        assert_eq!(outcome, DispatchOutcome { invoked: 1, cut_short: true });
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["pathfinding"]);
+
+        let outcome = event_manager.dispatch_with_deadline(&FrameStarted, Instant::now() + Duration::from_secs(60));
+        assert_eq!(outcome, DispatchOutcome { invoked: 3, cut_short: false });
+    }
+
+    struct FrameBegan;
+
+    #[test]
+    fn deadline_also_covers_aliased_types() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<&'static str>();
+        let tx_old = tx.clone();
+        event_manager.subscribe(move |_: &FrameBegan| {
+            let _ = tx_old.send("legacy hud");
+        });
+        event_manager.alias_event(|_: &FrameStarted| FrameBegan);
+
+        let outcome = event_manager.dispatch_with_deadline(&FrameStarted, Instant::now() + Duration::from_secs(60));
+        assert_eq!(outcome, DispatchOutcome { invoked: 1, cut_short: false });
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["legacy hud"]);
+
+        // A deadline that has already passed stops the aliased listeners too.
+        let outcome = event_manager.dispatch_with_deadline(&FrameStarted, Instant::now());
+        assert_eq!(outcome, DispatchOutcome { invoked: 0, cut_short: true });
+        assert!(rx.try_iter().next().is_none());
+    }
+}
@@ -0,0 +1,140 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{EventManager, ListenerEntry, Phase, SubscriptionId};
+
+// How one `subscribe`d listener shows up in the manager's `Debug` output.
+#[derive(Debug)]
+#[allow(dead_code)] // Only read through `Debug`.
+struct ListenerSummary {
+    id: SubscriptionId,
+    phase: Phase,
+    tag: Option<String>,
+    name: Option<&'static str>,
+}
+
+impl EventManager {
+    // Records the name of `T` for `Debug` output and type-erased dispatch.
+    pub(crate) fn remember_type<T: Any>(&mut self) {
+        self.type_names.entry(TypeId::of::<T>()).or_insert(std::any::type_name::<T>());
+    }
+
+    // The name recorded for `type_id`, or a placeholder for types only known by id, such as
+    // those passed to `wait_for_all` before anything subscribed to them.
+    pub(crate) fn type_name_of(&self, type_id: TypeId) -> &'static str {
+        self.type_names.get(&type_id).copied().unwrap_or("<unnamed type>")
+    }
+
+    fn listener_summaries(&self) -> Vec<(&'static str, Vec<ListenerSummary>)> {
+        let mut types: Vec<_> = self
+            .listeners
+            .iter()
+            .filter(|(_, bucket)| bucket.live_count() > 0)
+            .map(|(type_id, bucket)| {
+                let summaries = bucket
+                    .live_ids()
+                    .map(|id| ListenerSummary {
+                        id,
+                        phase: self.phases.get(type_id).and_then(|phases| phases.get(&id)).copied().unwrap_or(Phase::Update),
+                        tag: self.tags.get(type_id).and_then(|tags| tags.get(&id)).cloned(),
+                        name: self.names.get(type_id).and_then(|names| names.get(&id)).copied(),
+                    })
+                    .collect();
+                (self.type_name_of(*type_id), summaries)
+            })
+            .collect();
+        types.sort_by_key(|(name, _)| *name);
+        types
+    }
+
+    // Listener counts per event type name for one of the side pools.
+    fn pool_counts<L>(&self, pool: &HashMap<TypeId, Vec<ListenerEntry<L>>>) -> Vec<(&'static str, usize)> {
+        let mut counts: Vec<_> = pool
+            .iter()
+            .filter(|(_, entries)| !entries.is_empty())
+            .map(|(type_id, entries)| (self.type_name_of(*type_id), entries.len()))
+            .collect();
+        counts.sort();
+        counts
+    }
+
+    // Like `pool_counts`, for pools keyed by event and result type.
+    fn paired_pool_counts<L>(&self, pool: &HashMap<(TypeId, TypeId), Vec<ListenerEntry<L>>>) -> Vec<(String, usize)> {
+        let mut counts: Vec<_> = pool
+            .iter()
+            .filter(|(_, entries)| !entries.is_empty())
+            .map(|((event, output), entries)| {
+                (format!("{} -> {}", self.type_name_of(*event), self.type_name_of(*output)), entries.len())
+            })
+            .collect();
+        counts.sort();
+        counts
+    }
+}
+
+/// Prints every event type with listeners, by type name: each `subscribe`d listener with its
+/// id, phase, tag and name, then how many listeners the other pools hold per type.
+/// Use `{:#?}` for one entry per line.
+impl fmt::Debug for EventManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("EventManager");
+        debug
+            .field("listeners", &DebugMap(self.listener_summaries()))
+            .field("shared_listeners", &DebugMap(self.pool_counts(&self.shared_listeners)))
+            .field("verdicts", &DebugMap(self.pool_counts(&self.verdicts)))
+            .field("rewriters", &DebugMap(self.pool_counts(&self.rewriters)))
+            .field("consumers", &DebugMap(self.pool_counts(&self.consumers)))
+            .field("queries", &DebugMap(self.paired_pool_counts(&self.queries)))
+            .field("accumulators", &DebugMap(self.paired_pool_counts(&self.accumulators)))
+            .field("emitters", &DebugMap(self.paired_pool_counts(&self.emitters)));
+        #[cfg(any(test, feature = "async"))]
+        debug
+            .field("async_listeners", &DebugMap(self.pool_counts(&self.async_listeners)))
+            .field("async_queries", &DebugMap(self.paired_pool_counts(&self.async_queries)));
+        debug.finish_non_exhaustive()
+    }
+}
+
+// Prints sorted pairs as a map.
+struct DebugMap<K, V>(Vec<(K, V)>);
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for DebugMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.0.iter().map(|(key, value)| (key, value))).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct DoorOpened;
+    struct AlarmRaised;
+
+    #[test]
+    fn debug_output_lists_types_and_listener_metadata() {
+        let mut event_manager = EventManager::new();
+        event_manager.subscribe_named("play creak", |_: &DoorOpened| {});
+        event_manager.subscribe_tagged("ai", |_: &DoorOpened| {});
+        event_manager.subscribe_in_phase(Phase::PostUpdate, |_: &AlarmRaised| {});
+        event_manager.subscribe_fn(|_: &AlarmRaised| {});
+        event_manager.subscribe_query(|_: &DoorOpened| 3u8);
+
+        let output = format!("{event_manager:?}");
+        let door = std::any::type_name::<DoorOpened>();
+        let alarm = std::any::type_name::<AlarmRaised>();
+        assert!(output.starts_with("EventManager { listeners: {"), "{output}");
+        assert!(
+            output.contains(&format!(
+                "\"{door}\": [ListenerSummary {{ id: SubscriptionId(0), phase: Update, tag: None, name: Some(\"play creak\") }}, \
+                 ListenerSummary {{ id: SubscriptionId(1), phase: Update, tag: Some(\"ai\"), name: None }}]"
+            )),
+            "{output}"
+        );
+        assert!(output.contains("phase: PostUpdate"), "{output}");
+        assert!(output.contains(&format!("shared_listeners: {{\"{alarm}\": 1}}")), "{output}");
+        assert!(output.contains(&format!("queries: {{\"{door} -> u8\": 1}}")), "{output}");
+        assert!(format!("{:?}", EventManager::new()).contains("listeners: {}"));
+    }
+}
@@ -0,0 +1,119 @@
+use std::any::{Any, TypeId};
+use std::collections::VecDeque;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use crate::EventManager;
+
+// The last `capacity` events of one type that went through `dispatch_deduped`, with their
+// hashes so most comparisons never need to look at the events themselves.
+pub(crate) struct DedupWindow {
+    capacity: usize,
+    recent: VecDeque<(u64, Box<dyn Any>)>,
+}
+
+impl EventManager {
+    /// Opts `E` into deduplication: `dispatch_deduped` then skips an event identical to any
+    /// of the last `window` events of `E` passed to it. Calling again resizes the window and
+    /// forgets what it has seen. A `window` of 0 never skips anything.
+    pub fn enable_dedup<E: Any + 'static>(&mut self, window: usize) {
+        self.dedup.insert(
+            TypeId::of::<E>(),
+            DedupWindow {
+                capacity: window,
+                recent: VecDeque::with_capacity(window),
+            },
+        );
+    }
+
+    /// Opts `E` back out of deduplication.
+    pub fn disable_dedup<E: Any + 'static>(&mut self) {
+        self.dedup.remove(&TypeId::of::<E>());
+    }
+
+    /// Dispatches `event` unless an identical event was among the last events of `E` passed
+    /// here, as configured by `enable_dedup`. Events are compared with `==`, so events that
+    /// merely share a hash are both dispatched; the window keeps a clone of each event. Skipped
+    /// duplicates don't refresh the window. Types that were never enabled are always
+    /// dispatched. Returns whether the event was dispatched.
+    pub fn dispatch_deduped<E: Any + Hash + Eq + Clone + 'static>(&mut self, event: &E) -> bool {
+        if let Some(window) = self.dedup.get_mut(&TypeId::of::<E>()) {
+            let mut hasher = DefaultHasher::new();
+            event.hash(&mut hasher);
+            let hash = hasher.finish();
+
+            let seen = window
+                .recent
+                .iter()
+                .any(|(recent_hash, recent)| *recent_hash == hash && recent.downcast_ref::<E>() == Some(event));
+            if seen {
+                return false;
+            }
+            if window.capacity > 0 {
+                if window.recent.len() == window.capacity {
+                    window.recent.pop_front();
+                }
+                window.recent.push_back((hash, Box::new(event.clone())));
+            }
+        }
+        self.dispatch(event);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[derive(Clone, Hash, PartialEq, Eq)]
+    struct ButtonPressed {
+        button: u8,
+    }
+
+    #[test]
+    fn dispatch_deduped_skips_recent_duplicates() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<u8>();
+        event_manager.subscribe(move |event: &ButtonPressed| {
+            let _ = tx.send(event.button);
+        });
+
+        // Not opted in yet, so nothing is suppressed.
+        assert!(event_manager.dispatch_deduped(&ButtonPressed { button: 1 }));
+        assert!(event_manager.dispatch_deduped(&ButtonPressed { button: 1 }));
+
+        event_manager.enable_dedup::<ButtonPressed>(2);
+        for button in [1, 1, 2, 1, 3, 1] {
+            event_manager.dispatch_deduped(&ButtonPressed { button });
+        }
+
+        // The final 1 arrives after 1 has been pushed out of the window by 2 and 3.
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![1, 1, 1, 2, 3, 1]);
+
+        event_manager.disable_dedup::<ButtonPressed>();
+        assert!(event_manager.dispatch_deduped(&ButtonPressed { button: 1 }));
+    }
+
+    // Every value hashes the same, so only `==` can tell them apart.
+    #[derive(Clone, PartialEq, Eq)]
+    struct Colliding(u8);
+
+    impl Hash for Colliding {
+        fn hash<H: Hasher>(&self, _: &mut H) {}
+    }
+
+    #[test]
+    fn hash_collisions_are_still_dispatched() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<u8>();
+        event_manager.subscribe(move |event: &Colliding| {
+            let _ = tx.send(event.0);
+        });
+
+        event_manager.enable_dedup::<Colliding>(4);
+        for value in [1, 2, 1, 3, 2] {
+            event_manager.dispatch_deduped(&Colliding(value));
+        }
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,68 @@
+use std::any::Any;
+
+use crate::{EventEmitter, EventManager};
+
+/// Something `emit!` can raise events on. Outside a dispatch that is the `EventManager`,
+/// which dispatches the event immediately; inside a `subscribe_chaining` listener it is the
+/// listener's `EventEmitter`, which holds the event back until the running dispatch is done.
+pub trait Emit {
+    fn emit<E: Any + 'static>(&mut self, event: E);
+}
+
+impl Emit for EventManager {
+    fn emit<E: Any + 'static>(&mut self, event: E) {
+        self.dispatch(&event);
+    }
+}
+
+impl Emit for EventEmitter {
+    fn emit<E: Any + 'static>(&mut self, event: E) {
+        self.enqueue(event);
+    }
+}
+
+/// Raises an event without choosing between dispatching and queueing by hand:
+/// `emit!(manager, PlayerJumped)` dispatches at once, while `emit!(emitter, PlayerJumped)`
+/// from inside a chaining listener queues it behind the event being handled.
+///
+/// Listeners never get hold of the manager itself while it dispatches, so whether the
+/// event is deferred follows from what the caller holds and is settled at compile time.
+/// The difference still matters to the caller: after `emit!` on a manager every listener
+/// has run, while after `emit!` on an emitter none of them has yet.
+#[macro_export]
+macro_rules! emit {
+    ($target:expr, $event:expr $(,)?) => {{
+        use $crate::Emit as _;
+        $target.emit($event)
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    struct DoorOpened;
+    struct LightsOn;
+
+    #[test]
+    fn emit_dispatches_outside_and_defers_inside_a_dispatch() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<&str>();
+        let tx_door = tx.clone();
+        event_manager.subscribe_chaining(move |_: &DoorOpened, emitter| {
+            emit!(emitter, LightsOn);
+            let _ = tx_door.send("door opened");
+        });
+        event_manager.subscribe(move |_: &LightsOn| {
+            let _ = tx.send("lights on");
+        });
+
+        emit!(event_manager, LightsOn);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["lights on"]);
+
+        // Raised mid-dispatch, so it only runs once the door listener has returned.
+        emit!(event_manager, DoorOpened);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["door opened", "lights on"]);
+    }
+}
@@ -0,0 +1,160 @@
+use std::any::{Any, TypeId};
+
+use crate::{EventManager, SubscriptionId};
+
+/// Metadata for an event type, usually implemented with `#[derive(Event)]`:
+///
+/// ```
+/// use event_forge::Event;
+///
+/// #[derive(Debug, Event)]
+/// #[event(name = "player.jumped", category = "gameplay", priority = -5, debug)]
+/// struct PlayerJumped {
+///     height: f32,
+/// }
+///
+/// assert_eq!(PlayerJumped::NAME, "player.jumped");
+/// assert_eq!(PlayerJumped::PRIORITY, -5);
+/// assert_eq!(PlayerJumped { height: 2.0 }.describe(), "PlayerJumped { height: 2.0 }");
+/// ```
+///
+/// Any `'static` type can still be dispatched without it; implementing it lets
+/// `dispatch_event` report the event by name and filter it by category, and lets
+/// `subscribe_event` apply the type's default priority.
+pub trait Event: Any {
+    /// A readable, stable name, e.g. for logs. The derive uses the type's name.
+    const NAME: &'static str;
+    /// A coarse grouping such as "input" or "network", for filtering.
+    const CATEGORY: Option<&'static str> = None;
+    /// The priority `subscribe_event` listeners of this type get, so an ordering convention
+    /// can live with the type instead of with every subscriber.
+    const PRIORITY: i32 = 0;
+
+    /// Describes this event for diagnostics. Its `NAME` unless overridden; the derive's
+    /// `#[event(debug)]` uses `Debug` instead.
+    fn describe(&self) -> String {
+        Self::NAME.to_string()
+    }
+}
+
+impl EventManager {
+    /// Subscribes a listener to `E` at the type's default `E::PRIORITY`, like
+    /// `subscribe_with_priority(E::PRIORITY, ..)`. Call `subscribe_with_priority` directly to
+    /// override it; plain `subscribe` always uses priority 0.
+    pub fn subscribe_event<E: Event>(&mut self, listener: impl FnMut(&E) + 'static) -> SubscriptionId {
+        self.subscribe_with_priority(E::PRIORITY, listener)
+    }
+
+    /// Dispatches `event` like `dispatch`, unless its category was muted with
+    /// `mute_category`. Stats and slow-dispatch reports count this dispatch under `E::NAME`
+    /// instead of the Rust type name; other dispatches of `E` and the manager's `Debug`
+    /// output keep using the type name. Returns whether it was dispatched.
+    pub fn dispatch_event<E: Event>(&mut self, event: &E) -> bool {
+        if E::CATEGORY.is_some_and(|category| self.muted_categories.contains(category)) {
+            return false;
+        }
+        self.dispatch_erased(TypeId::of::<E>(), E::NAME, event as &dyn Any);
+        true
+    }
+
+    /// Makes `dispatch_event` drop events of `category`, e.g. to silence noisy debug
+    /// events. Other dispatch methods ignore categories.
+    pub fn mute_category(&mut self, category: &str) {
+        self.muted_categories.insert(category.to_string());
+    }
+
+    /// Lets events of `category` through `dispatch_event` again.
+    pub fn unmute_category(&mut self, category: &str) {
+        self.muted_categories.remove(category);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    // The crate root also exports the derive macro under this name.
+    use crate::Event;
+    use std::sync::mpsc;
+
+    #[derive(Event)]
+    struct DoorOpened;
+
+    #[derive(Debug, Event)]
+    #[event(name = "net.packet_lost", category = "network", debug)]
+    struct PacketLost {
+        sequence: u32,
+    }
+
+    #[derive(Event)]
+    #[event(priority = 10)]
+    struct DamageApplied;
+
+    #[test]
+    fn derived_metadata() {
+        assert_eq!(DoorOpened::NAME, "DoorOpened");
+        assert_eq!(DoorOpened::CATEGORY, None);
+        assert_eq!(DoorOpened::PRIORITY, 0);
+        assert_eq!(DamageApplied::PRIORITY, 10);
+        assert_eq!(DoorOpened.describe(), "DoorOpened");
+
+        assert_eq!(PacketLost::NAME, "net.packet_lost");
+        assert_eq!(PacketLost::CATEGORY, Some("network"));
+        assert_eq!(PacketLost { sequence: 9 }.describe(), "PacketLost { sequence: 9 }");
+    }
+
+    #[test]
+    fn categories_filter_dispatch_event() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+        event_manager.subscribe(move |event: &PacketLost| {
+            let _ = tx.send(event.sequence);
+        });
+
+        assert!(event_manager.dispatch_event(&PacketLost { sequence: 1 }));
+        event_manager.mute_category("network");
+        assert!(!event_manager.dispatch_event(&PacketLost { sequence: 2 }));
+        event_manager.dispatch(&PacketLost { sequence: 3 });
+        event_manager.unmute_category("network");
+        assert!(event_manager.dispatch_event(&PacketLost { sequence: 4 }));
+
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![1, 3, 4]);
+    }
+
+    #[test]
+    fn dispatch_event_names_only_its_own_dispatches() {
+        let mut event_manager = EventManager::new();
+        event_manager.subscribe(|_: &PacketLost| {});
+        event_manager.enable_stats(1);
+
+        event_manager.dispatch_event(&PacketLost { sequence: 1 });
+        event_manager.dispatch_event(&PacketLost { sequence: 2 });
+        event_manager.dispatch(&PacketLost { sequence: 3 });
+
+        let type_name = std::any::type_name::<PacketLost>();
+        let counts = event_manager.counts_by_name();
+        assert_eq!(counts.get("net.packet_lost"), Some(&2));
+        assert_eq!(counts.get(type_name), Some(&1));
+        let debug = format!("{event_manager:?}");
+        assert!(debug.contains(type_name) && !debug.contains("net.packet_lost"));
+    }
+
+    #[test]
+    fn subscribe_event_uses_the_default_priority() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+        let listener = |name: &'static str| {
+            let tx = tx.clone();
+            move |_: &DamageApplied| {
+                let _ = tx.send(name);
+            }
+        };
+
+        event_manager.subscribe(listener("plain"));
+        event_manager.subscribe_event(listener("default"));
+        event_manager.subscribe_with_priority(20, listener("override"));
+        event_manager.subscribe_event(listener("default again"));
+
+        event_manager.dispatch(&DamageApplied);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["override", "default", "default again", "plain"]);
+    }
+}
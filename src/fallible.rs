@@ -0,0 +1,237 @@
+use std::any::{Any, TypeId};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use crate::{EventManager, ListenerEntry, Step, SubscriptionId};
+
+// Errors reported by fallible listeners, waiting for `take_errors`.
+pub(crate) type ErrorSink = Vec<(SubscriptionId, Box<dyn Any>)>;
+
+impl EventManager {
+    /// Subscribes a fallible listener to `E` that stays subscribed until it first returns
+    /// `Err`. It is then removed once the current dispatch finishes, leaving the order of the
+    /// remaining listeners unchanged, and the error is kept for `take_errors::<Failure>()`.
+    pub fn subscribe_until_error<E: Any + 'static, Failure: Any + 'static>(
+        &mut self,
+        mut listener: impl FnMut(&E) -> Result<(), Failure> + 'static,
+    ) -> SubscriptionId {
+        let id = self.allocate_id();
+        let sink = self.error_sink();
+        let retired = Rc::new(Cell::new(false));
+        let retired_flag = Rc::clone(&retired);
+
+        let boxed_listener = Box::new(move |event: &dyn Any| {
+            let Some(specific_event) = event.downcast_ref::<E>() else {
+                return;
+            };
+            if let Err(err) = listener(specific_event) {
+                retired_flag.set(true);
+                sink.borrow_mut().push((id, Box::new(err)));
+            }
+        });
+
+        self.remember_type::<E>();
+        self.insert_listener(TypeId::of::<E>(), ListenerEntry { id, listener: boxed_listener, retired: Some(retired) });
+        id
+    }
+
+    /// Subscribes a fallible listener to `E`. It stays subscribed whatever it returns; each
+    /// `Err` is kept for `take_errors::<Failure>()`, and `dispatch_with_retry` retries it.
+    pub fn subscribe_fallible<E: Any + 'static, Failure: Any + 'static>(
+        &mut self,
+        mut listener: impl FnMut(&E) -> Result<(), Failure> + 'static,
+    ) -> SubscriptionId {
+        let id = self.allocate_id();
+        let sink = self.error_sink();
+
+        let boxed_listener = Box::new(move |event: &dyn Any| {
+            let Some(specific_event) = event.downcast_ref::<E>() else {
+                return;
+            };
+            if let Err(err) = listener(specific_event) {
+                sink.borrow_mut().push((id, Box::new(err)));
+            }
+        });
+
+        self.remember_type::<E>();
+        self.insert_listener(TypeId::of::<E>(), ListenerEntry { id, listener: boxed_listener, retired: None });
+        id
+    }
+
+    /// Dispatches `event` like `dispatch`, then retries each fallible listener that failed,
+    /// on its own and with the same event, until it succeeds or has run `max_attempts` times
+    /// in total. Retries happen in rounds after the first full pass, in dispatch order, and
+    /// nothing that succeeded runs again. Only the last error of each listener that never
+    /// succeeded is kept for `take_errors`, and their ids are returned.
+    /// `subscribe_until_error` listeners are removed by their first error, so they are never retried.
+    pub fn dispatch_with_retry<E: Any + 'static>(&mut self, event: &E, max_attempts: usize) -> Vec<SubscriptionId> {
+        let type_id = TypeId::of::<E>();
+        let type_name = std::any::type_name::<E>();
+        let marks = self.error_marks();
+        self.dispatch_selected(type_id, type_name, event, None);
+        let mut failed = self.errors_since(&marks);
+
+        let mut gave_up = Vec::new();
+        for _ in 1..max_attempts {
+            if failed.is_empty() {
+                break;
+            }
+            let mut still_failing = Vec::new();
+            for (target, err) in failed {
+                let marks = self.error_marks();
+                let mut ran = false;
+                self.dispatch_selected(
+                    type_id,
+                    type_name,
+                    event,
+                    Some(&mut |_, id| {
+                        if id == target {
+                            ran = true;
+                            Step::Run
+                        } else {
+                            Step::Skip
+                        }
+                    }),
+                );
+                if !ran {
+                    gave_up.push((target, err));
+                } else if let Some(retried) = self.errors_since(&marks).into_iter().find(|(id, _)| *id == target) {
+                    still_failing.push(retried);
+                }
+            }
+            failed = still_failing;
+        }
+        gave_up.extend(failed);
+        self.flush_chained();
+
+        let sink = self.error_sink();
+        let ids = gave_up.iter().map(|(id, _)| *id).collect();
+        sink.borrow_mut().extend(gave_up);
+        ids
+    }
+
+    // How many errors each sink holds, to find the ones reported after this point.
+    fn error_marks(&self) -> Vec<usize> {
+        self.error_sinks.iter().map(|sink| sink.borrow().len()).collect()
+    }
+
+    // Removes the errors reported since `marks` was taken, one per listener, keeping its last.
+    fn errors_since(&self, marks: &[usize]) -> Vec<(SubscriptionId, Box<dyn Any>)> {
+        let mut errors: Vec<(SubscriptionId, Box<dyn Any>)> = Vec::new();
+        for (index, sink) in self.error_sinks.iter().enumerate() {
+            let mark = marks.get(index).copied().unwrap_or(0);
+            for (id, err) in sink.borrow_mut().split_off(mark) {
+                match errors.iter_mut().find(|(failed, _)| *failed == id) {
+                    Some(slot) => slot.1 = err,
+                    None => errors.push((id, err)),
+                }
+            }
+        }
+        errors
+    }
+
+    /// Removes and returns the errors of type `Failure` reported by fallible listeners so far,
+    /// oldest first, with the id of the listener that failed. Errors of other types are kept.
+    pub fn take_errors<Failure: Any + 'static>(&mut self) -> Vec<(SubscriptionId, Failure)> {
+        let mut taken = Vec::new();
+        for sink in &self.error_sinks {
+            let mut sink = sink.borrow_mut();
+            let mut kept = Vec::new();
+            for (id, err) in sink.drain(..) {
+                match err.downcast::<Failure>() {
+                    Ok(err) => taken.push((id, *err)),
+                    Err(err) => kept.push((id, err)),
+                }
+            }
+            *sink = kept;
+        }
+        taken
+    }
+
+    // The sink fallible listeners of this manager report into; created on first use.
+    pub(crate) fn error_sink(&mut self) -> Rc<RefCell<ErrorSink>> {
+        if self.error_sinks.is_empty() {
+            self.error_sinks.push(Rc::default());
+        }
+        Rc::clone(&self.error_sinks[0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    struct Packet(u32);
+
+    #[derive(Debug, PartialEq)]
+    struct ConnectionLost(u32);
+
+    #[test]
+    fn listener_is_removed_after_its_first_error() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<String>();
+
+        let tx_first = tx.clone();
+        event_manager.subscribe(move |event: &Packet| {
+            let _ = tx_first.send(format!("log {}", event.0));
+        });
+        let tx_forward = tx.clone();
+        let mut calls = 0;
+        let forwarder = event_manager.subscribe_until_error(move |event: &Packet| {
+            calls += 1;
+            if calls == 2 {
+                return Err(ConnectionLost(event.0));
+            }
+            let _ = tx_forward.send(format!("forward {}", event.0));
+            Ok(())
+        });
+        let tx_last = tx.clone();
+        event_manager.subscribe(move |event: &Packet| {
+            let _ = tx_last.send(format!("ack {}", event.0));
+        });
+
+        for packet in 1..=3 {
+            event_manager.dispatch(&Packet(packet));
+        }
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            vec!["log 1", "forward 1", "ack 1", "log 2", "ack 2", "log 3", "ack 3"]
+        );
+
+        assert!(event_manager.take_errors::<String>().is_empty());
+        assert_eq!(event_manager.take_errors::<ConnectionLost>(), vec![(forwarder, ConnectionLost(2))]);
+        assert!(event_manager.take_errors::<ConnectionLost>().is_empty());
+    }
+
+    #[test]
+    fn failing_listener_is_retried_until_it_succeeds() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<String>();
+
+        let tx_log = tx.clone();
+        event_manager.subscribe(move |event: &Packet| {
+            let _ = tx_log.send(format!("log {}", event.0));
+        });
+        let tx_upload = tx.clone();
+        let mut attempts = 0;
+        event_manager.subscribe_fallible(move |event: &Packet| {
+            attempts += 1;
+            let _ = tx_upload.send(format!("upload {} attempt {attempts}", event.0));
+            if attempts <= 2 {
+                return Err(ConnectionLost(attempts));
+            }
+            Ok(())
+        });
+        let flaky = event_manager.subscribe_fallible(|event: &Packet| Err(ConnectionLost(event.0)));
+
+        let gave_up = event_manager.dispatch_with_retry(&Packet(7), 3);
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            vec!["log 7", "upload 7 attempt 1", "upload 7 attempt 2", "upload 7 attempt 3"]
+        );
+        // Only the listener that never succeeded is reported, once.
+        assert_eq!(gave_up, vec![flaky]);
+        assert_eq!(event_manager.take_errors::<ConnectionLost>(), vec![(flaky, ConnectionLost(7))]);
+    }
+}
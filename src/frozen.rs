@@ -0,0 +1,182 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+use crate::{alias, chain, propagation, EventManager, ListenerBucket, ListenerEntry, SharedListener, Step};
+
+// Both listener pools of one event type, so a frozen dispatch needs a single lookup.
+#[derive(Default)]
+struct FrozenListeners {
+    mutable: ListenerBucket,
+    shared: Vec<ListenerEntry<SharedListener>>,
+}
+
+/// A dispatch-only view of an `EventManager`, created by `EventManager::freeze`.
+///
+/// The listener set can't change while frozen, so `dispatch` skips the bookkeeping a
+/// mutable manager does: no stats, no pruning of self-removed listeners, no lifecycle
+/// checks. Call `thaw` to get the full manager back.
+pub struct FrozenEventManager {
+    listeners: HashMap<TypeId, FrozenListeners>,
+    // Everything except the listeners, untouched until `thaw`.
+    rest: EventManager,
+}
+
+impl EventManager {
+    /// Consumes the manager and compacts its listeners into a `FrozenEventManager`, in their
+    /// current dispatch order. Verdicts, queries, emitters and all other state are kept as they
+    /// are but can't be used until the manager is thawed. Lifecycle hooks don't fire.
+    pub fn freeze(mut self) -> FrozenEventManager {
+        let mut listeners: HashMap<TypeId, FrozenListeners> = HashMap::new();
+        let mut pruned = Vec::new();
+        for (type_id, mut bucket) in self.listeners.drain() {
+            pruned.push((type_id, bucket.prune_retired()));
+            bucket.shrink_to_fit();
+            listeners.entry(type_id).or_default().mutable = bucket;
+        }
+        for (type_id, ids) in pruned {
+            self.forget_listeners(type_id, &ids);
+        }
+        for (type_id, mut entries) in std::mem::take(&mut self.shared_listeners) {
+            entries.shrink_to_fit();
+            listeners.entry(type_id).or_default().shared = entries;
+        }
+        listeners.retain(|_, frozen| !frozen.mutable.is_empty() || !frozen.shared.is_empty());
+        listeners.shrink_to_fit();
+
+        FrozenEventManager { listeners, rest: self }
+    }
+}
+
+impl FrozenEventManager {
+    /// Dispatches an event to every listener for `E`, mutable listeners first, exactly like
+    /// `EventManager::dispatch` minus the bookkeeping. A listener that retired itself while
+    /// frozen (e.g. one installed by `wait_for_all`) is skipped but only dropped on `thaw`.
+    /// Follow-ups from `subscribe_chaining` listeners are still dispatched afterwards.
+    pub fn dispatch<E: Any + 'static>(&mut self, event: &E) {
+        self.dispatch_erased(TypeId::of::<E>(), event);
+        if self.rest.outboxes.is_empty() {
+            return;
+        }
+        loop {
+            let batch = chain::take_pending(&self.rest.outboxes);
+            if batch.is_empty() {
+                return;
+            }
+            for (type_id, _, event) in batch {
+                self.dispatch_erased(type_id, &*event);
+            }
+        }
+    }
+
+    fn dispatch_erased(&mut self, type_id: TypeId, event: &dyn Any) {
+        self.run_listeners(type_id, event);
+        if !self.rest.aliases.is_empty() {
+            for (target, _, event) in alias::convert_aliases(&self.rest.aliases, type_id, event) {
+                self.run_listeners(target, &*event);
+            }
+        }
+    }
+
+    fn run_listeners(&mut self, type_id: TypeId, event: &dyn Any) {
+        let Some(frozen) = self.listeners.get_mut(&type_id) else {
+            return;
+        };
+        if self.rest.stop_flags.is_empty() {
+            frozen.mutable.run(event, &mut 0, None);
+            for entry in &frozen.shared {
+                (entry.listener)(event);
+            }
+            return;
+        }
+
+        let stop_flags = &self.rest.stop_flags;
+        propagation::reset(stop_flags);
+        frozen.mutable.run(event, &mut 0, Some(&mut |_, _| if propagation::stopped(stop_flags) { Step::Stop } else { Step::Run }));
+        for entry in &frozen.shared {
+            if propagation::stopped(stop_flags) {
+                return;
+            }
+            (entry.listener)(event);
+        }
+    }
+
+    /// Turns this back into a regular `EventManager` with the same listeners, ids and order.
+    pub fn thaw(self) -> EventManager {
+        let FrozenEventManager { listeners, mut rest } = self;
+        for (type_id, frozen) in listeners {
+            let mut bucket = frozen.mutable;
+            let pruned = bucket.prune_retired();
+            rest.forget_listeners(type_id, &pruned);
+            if !bucket.is_empty() {
+                *rest.listeners.get_or_default(type_id) = bucket;
+            }
+            if !frozen.shared.is_empty() {
+                rest.shared_listeners.insert(type_id, frozen.shared);
+            }
+        }
+        let hooked: Vec<TypeId> = rest.lifecycle.keys().copied().collect();
+        for type_id in hooked {
+            rest.sync_lifecycle(type_id);
+        }
+        rest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    struct Tick(u32);
+
+    #[test]
+    fn frozen_manager_dispatches_and_thaws_back() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<(&'static str, u32)>();
+
+        let tx_shared = tx.clone();
+        event_manager.subscribe_fn(move |event: &Tick| {
+            let _ = tx_shared.send(("shared", event.0));
+        });
+        let tx_mut = tx.clone();
+        let physics = event_manager.subscribe(move |event: &Tick| {
+            let _ = tx_mut.send(("physics", event.0));
+        });
+
+        let mut frozen = event_manager.freeze();
+        frozen.dispatch(&Tick(1));
+        frozen.dispatch(&0u8);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![("physics", 1), ("shared", 1)]);
+
+        let mut event_manager = frozen.thaw();
+        let tx_ai = tx.clone();
+        let ai = event_manager
+            .subscribe_after(physics, move |event: &Tick| {
+                let _ = tx_ai.send(("ai", event.0));
+            })
+            .unwrap();
+        assert!(ai > physics, "Ids keep counting after a thaw");
+
+        event_manager.dispatch(&Tick(2));
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            vec![("physics", 2), ("ai", 2), ("shared", 2)]
+        );
+    }
+
+    struct LegacyTick(u32);
+
+    #[test]
+    fn frozen_dispatch_follows_aliases() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<u32>();
+        event_manager.subscribe(move |event: &LegacyTick| {
+            let _ = tx.send(event.0);
+        });
+        event_manager.alias_event(|event: &Tick| LegacyTick(event.0));
+
+        let mut frozen = event_manager.freeze();
+        frozen.dispatch(&Tick(7));
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![7]);
+    }
+}
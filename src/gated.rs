@@ -0,0 +1,52 @@
+use std::any::Any;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::{EventManager, SubscriptionId};
+
+impl EventManager {
+    /// Subscribes a listener to `E` that only runs while `gate` is true, so a subsystem can be
+    /// paused from any thread holding the flag. The listener stays subscribed and keeps its
+    /// place while paused.
+    ///
+    /// The flag is read with `Ordering::Acquire` on every dispatch: anything written before
+    /// the flag was set to true with `Ordering::Release` (or stronger) is visible to the listener.
+    pub fn subscribe_gated<E: Any + 'static>(&mut self, gate: Arc<AtomicBool>, mut listener: impl FnMut(&E) + 'static) -> SubscriptionId {
+        self.subscribe(move |event: &E| {
+            if gate.load(Ordering::Acquire) {
+                listener(event);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::thread;
+
+    struct Tick(u32);
+
+    #[test]
+    fn gate_pauses_and_resumes_the_listener() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+        let physics_enabled = Arc::new(AtomicBool::new(true));
+
+        event_manager.subscribe_gated(Arc::clone(&physics_enabled), move |tick: &Tick| {
+            let _ = tx.send(tick.0);
+        });
+
+        event_manager.dispatch(&Tick(1));
+        physics_enabled.store(false, Ordering::Release);
+        event_manager.dispatch(&Tick(2));
+
+        // The flag may be flipped from another thread.
+        let remote = Arc::clone(&physics_enabled);
+        thread::spawn(move || remote.store(true, Ordering::Release)).join().unwrap();
+        event_manager.dispatch(&Tick(3));
+
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![1, 3]);
+    }
+}
@@ -0,0 +1,118 @@
+use std::any::{Any, TypeId};
+use std::cell::Cell;
+use std::rc::Rc;
+
+use crate::{EventManager, SubscriptionId};
+
+/// A listener subscribed with `subscribe_guarded`, which stays subscribed only as long as
+/// this guard lives. Keep it in whatever owns the listener, e.g. a UI widget or a game
+/// entity, so despawning the owner also unsubscribes it.
+#[must_use = "dropping the guard unsubscribes the listener right away"]
+pub struct Subscription {
+    id: SubscriptionId,
+    // `None` once detached.
+    retired: Option<Rc<Cell<bool>>>,
+}
+
+impl Subscription {
+    pub fn id(&self) -> SubscriptionId {
+        self.id
+    }
+
+    /// Keeps the listener subscribed for good, like one added with `subscribe`. It can still
+    /// be removed with `unsubscribe` and the returned id.
+    pub fn detach(mut self) -> SubscriptionId {
+        self.retired = None;
+        self.id
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        if let Some(retired) = &self.retired {
+            retired.set(true);
+        }
+    }
+}
+
+impl EventManager {
+    /// Subscribes a listener to `E` like `subscribe`, returning a guard that unsubscribes it
+    /// when dropped. The listener stops running and counting as a listener of `E` as soon as
+    /// the guard is gone; it is freed on the next dispatch of `E`, like any listener that
+    /// removes itself. The guard can't reach the manager, so if it held the last listener,
+    /// the `on_last` lifecycle hook of `E` only fires on the next dispatch of or subscription
+    /// to `E`.
+    pub fn subscribe_guarded<E: Any + 'static>(&mut self, mut listener: impl FnMut(&E) + 'static) -> Subscription {
+        let retired = Rc::new(Cell::new(false));
+        let boxed_listener = Box::new(move |event: &dyn Any| {
+            if let Some(specific_event) = event.downcast_ref::<E>() {
+                listener(specific_event);
+            }
+        });
+        self.remember_type::<E>();
+        let id = self.push_listener(TypeId::of::<E>(), boxed_listener, Some(Rc::clone(&retired)));
+        Subscription { id, retired: Some(retired) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    struct Resized {
+        width: u32,
+    }
+
+    #[test]
+    fn dropping_the_guard_unsubscribes() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+
+        let tx_widget = tx.clone();
+        let widget = event_manager.subscribe_guarded(move |event: &Resized| {
+            let _ = tx_widget.send(("widget", event.width));
+        });
+        let tx_window = tx.clone();
+        let window = event_manager
+            .subscribe_guarded(move |event: &Resized| {
+                let _ = tx_window.send(("window", event.width));
+            })
+            .detach();
+
+        event_manager.dispatch(&Resized { width: 800 });
+        assert_eq!(event_manager.listener_ids::<Resized>(), vec![widget.id(), window]);
+        drop(widget);
+        assert_eq!(event_manager.listener_ids::<Resized>(), vec![window]);
+        event_manager.dispatch(&Resized { width: 640 });
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![("widget", 800), ("window", 800), ("window", 640)]);
+
+        assert!(event_manager.unsubscribe(window));
+        assert!(event_manager.is_empty());
+    }
+
+    #[test]
+    fn last_guard_fires_on_last_at_the_next_dispatch_or_subscription() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+        let tx_last = tx.clone();
+        event_manager.set_lifecycle_hooks::<Resized>(
+            move || {
+                let _ = tx.send("first");
+            },
+            move || {
+                let _ = tx_last.send("last");
+            },
+        );
+
+        drop(event_manager.subscribe_guarded(|_: &Resized| {}));
+        assert!(!event_manager.has_listeners::<Resized>());
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["first"], "on_last waits for the manager");
+        event_manager.dispatch(&Resized { width: 320 });
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["last"]);
+
+        drop(event_manager.subscribe_guarded(|_: &Resized| {}));
+        event_manager.subscribe_fn(|_: &Resized| {});
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["first", "last", "first"]);
+    }
+}
@@ -0,0 +1,84 @@
+use std::any::TypeId;
+
+use crate::EventManager;
+
+// The optional callbacks set by `set_pre_dispatch` and `set_post_dispatch`.
+#[derive(Default)]
+pub(crate) struct DispatchHooks {
+    pub(crate) pre: Option<Box<dyn FnMut(TypeId)>>,
+    pub(crate) post: Option<Box<dyn FnMut(TypeId, usize)>>,
+}
+
+impl EventManager {
+    /// Sets a callback run with the event's `TypeId` before every dispatch to `subscribe`
+    /// and `subscribe_fn` listeners, whichever method started it: plain, traced,
+    /// sampled, grouped, chained follow-ups, and each retry of `dispatch_with_retry`.
+    /// A dispatch that follows aliases calls it once per type it reaches, with that type's
+    /// `TypeId`, the dispatched type first. Verdict, query, rewriting, consuming and
+    /// emitting dispatches don't call it, and neither do `dispatch_shared`, which can't run
+    /// `FnMut` callbacks through `&self`, and `FrozenEventManager::dispatch`.
+    /// Replaces any previous callback.
+    pub fn set_pre_dispatch(&mut self, hook: impl FnMut(TypeId) + 'static) {
+        self.dispatch_hooks.pre = Some(Box::new(hook));
+    }
+
+    /// Sets a callback run after the same dispatches as `set_pre_dispatch`, with the event's
+    /// `TypeId` and how many listeners it invoked. Replaces any previous callback.
+    pub fn set_post_dispatch(&mut self, hook: impl FnMut(TypeId, usize) + 'static) {
+        self.dispatch_hooks.post = Some(Box::new(hook));
+    }
+
+    /// Removes both dispatch callbacks.
+    pub fn clear_dispatch_hooks(&mut self) {
+        self.dispatch_hooks = DispatchHooks::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    struct DoorOpened;
+    struct LightsOff;
+
+    #[test]
+    fn hooks_fire_around_each_dispatch() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+
+        let tx_pre = tx.clone();
+        event_manager.set_pre_dispatch(move |type_id| {
+            let _ = tx_pre.send(("pre", type_id, None));
+        });
+        let tx_post = tx.clone();
+        event_manager.set_post_dispatch(move |type_id, invoked| {
+            let _ = tx_post.send(("post", type_id, Some(invoked)));
+        });
+
+        event_manager.subscribe(|_: &DoorOpened| {});
+        event_manager.subscribe_fn(|_: &DoorOpened| {});
+        event_manager.dispatch(&DoorOpened);
+        event_manager.dispatch_traced(&LightsOff);
+
+        let door = TypeId::of::<DoorOpened>();
+        let lights = TypeId::of::<LightsOff>();
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            vec![("pre", door, None), ("post", door, Some(2)), ("pre", lights, None), ("post", lights, Some(0))]
+        );
+
+        // Aliased types get a dispatch of their own, and `dispatch_shared` calls neither hook.
+        event_manager.alias_event(|_: &LightsOff| DoorOpened);
+        event_manager.dispatch(&LightsOff);
+        event_manager.dispatch_shared(&DoorOpened);
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            vec![("pre", lights, None), ("post", lights, Some(0)), ("pre", door, None), ("post", door, Some(2))]
+        );
+
+        event_manager.clear_dispatch_hooks();
+        event_manager.dispatch(&DoorOpened);
+        assert!(rx.try_iter().next().is_none());
+    }
+}
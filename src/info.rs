@@ -0,0 +1,83 @@
+use std::any::{Any, TypeId};
+
+use crate::{EventManager, Phase, SubscriptionId};
+
+/// What `subscription_info` knows about one listener.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubscriptionInfo {
+    pub id: SubscriptionId,
+    /// The name given with `subscribe_named`.
+    pub name: Option<&'static str>,
+    /// The tag group given with `subscribe_tagged`.
+    pub group: Option<String>,
+    /// The priority given with `subscribe_with_priority`, 0 for other listeners.
+    pub priority: i32,
+    /// The phase the listener runs in, `Phase::Update` unless subscribed with
+    /// `subscribe_in_phase`. `None` for `subscribe_fn` listeners, which run after every phase.
+    pub phase: Option<Phase>,
+    /// Where the listener runs in `dispatch`, as in `listener_ids`.
+    pub position: usize,
+    /// Whether the listener removes itself, as `subscribe_once` listeners do after their first
+    /// event. Also set for other listeners that can retire, such as `subscribe_guarded` ones.
+    pub once: bool,
+}
+
+impl EventManager {
+    /// Returns everything recorded about the `subscribe` or `subscribe_fn` listener `id` of
+    /// `E` in one go, for debug tooling. Returns `None` if `E` has no such listener, including
+    /// when `id` belongs to another event type or to another kind of listener.
+    pub fn subscription_info<E: Any + 'static>(&self, id: SubscriptionId) -> Option<SubscriptionInfo> {
+        let type_id = TypeId::of::<E>();
+        let position = self.listener_ids::<E>().iter().position(|listener| *listener == id)?;
+        let shared = self.shared_listeners.get(&type_id).is_some_and(|shared| shared.iter().any(|entry| entry.id == id));
+        let phase = self.phases.get(&type_id).and_then(|phases| phases.get(&id)).copied().unwrap_or(Phase::Update);
+        Some(SubscriptionInfo {
+            id,
+            name: self.names.get(&type_id).and_then(|names| names.get(&id)).copied(),
+            group: self.tags.get(&type_id).and_then(|tags| tags.get(&id)).cloned(),
+            priority: self.priority_of(type_id, id),
+            phase: (!shared).then_some(phase),
+            position,
+            once: self.listeners.get(&type_id).is_some_and(|bucket| bucket.is_self_removing(id)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FrameRendered;
+
+    #[test]
+    fn info_combines_every_kind_of_metadata() {
+        let mut event_manager = EventManager::new();
+        let overlay = event_manager.subscribe_fn(|_: &FrameRendered| {});
+        let profiler = event_manager.subscribe_named("profiler", |_: &FrameRendered| {});
+        let hud = event_manager.subscribe_tagged("ui", |_: &FrameRendered| {});
+        let culling = event_manager.subscribe_in_phase(Phase::PreUpdate, |_: &FrameRendered| {});
+        let validation = event_manager.subscribe_with_priority(5, |_: &FrameRendered| {});
+        let other = event_manager.subscribe(|_: &u32| {});
+
+        let info = |id| event_manager.subscription_info::<FrameRendered>(id).unwrap();
+        assert_eq!(
+            info(profiler),
+            SubscriptionInfo { id: profiler, name: Some("profiler"), group: None, priority: 0, phase: Some(Phase::Update), position: 2, once: false }
+        );
+        assert_eq!(
+            info(hud),
+            SubscriptionInfo { id: hud, name: None, group: Some("ui".to_string()), priority: 0, phase: Some(Phase::Update), position: 3, once: false }
+        );
+        assert_eq!(info(culling).phase, Some(Phase::PreUpdate));
+        assert_eq!(info(culling).position, 0);
+        assert_eq!((info(validation).priority, info(validation).position), (5, 1));
+        assert_eq!((info(overlay).phase, info(overlay).position), (None, 4));
+        let reload = event_manager.subscribe_once(|_: &FrameRendered| {});
+        assert!(event_manager.subscription_info::<FrameRendered>(reload).unwrap().once);
+        assert!(!event_manager.subscription_info::<FrameRendered>(validation).unwrap().once);
+
+        assert_eq!(event_manager.subscription_info::<FrameRendered>(other), None);
+        assert!(event_manager.unsubscribe(hud));
+        assert_eq!(event_manager.subscription_info::<FrameRendered>(hud), None);
+    }
+}
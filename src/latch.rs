@@ -0,0 +1,71 @@
+use std::any::{Any, TypeId};
+
+use crate::{EventManager, SubscriptionId};
+
+impl EventManager {
+    /// Dispatches `event` like `dispatch` and keeps a copy as the latest state of `E`, which
+    /// `subscribe_latching` listeners receive as soon as they subscribe. Each call replaces
+    /// the previous copy.
+    pub fn dispatch_latching<E: Any + Clone + 'static>(&mut self, event: &E) {
+        self.latched.insert(TypeId::of::<E>(), Box::new(event.clone()));
+        self.dispatch(event);
+    }
+
+    /// Subscribes a listener to `E` like `subscribe`, first calling it with the event last
+    /// passed to `dispatch_latching`, if any. When nothing was latched yet it just waits for
+    /// the next dispatch.
+    pub fn subscribe_latching<E: Any + 'static>(&mut self, mut listener: impl FnMut(&E) + 'static) -> SubscriptionId {
+        if let Some(latched) = self.latched.get(&TypeId::of::<E>()).and_then(|event| event.downcast_ref::<E>()) {
+            listener(latched);
+        }
+        self.subscribe(listener)
+    }
+
+    /// Forgets the latched event of `E`, so later `subscribe_latching` listeners wait for the
+    /// next dispatch.
+    pub fn clear_latched<E: Any + 'static>(&mut self) {
+        self.latched.remove(&TypeId::of::<E>());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[derive(Clone)]
+    struct ConnectionStatusChanged {
+        online: bool,
+    }
+
+    #[test]
+    fn late_subscribers_receive_the_latched_event() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+
+        let tx_early = tx.clone();
+        event_manager.subscribe_latching(move |event: &ConnectionStatusChanged| {
+            let _ = tx_early.send(("early", event.online));
+        });
+        assert!(rx.try_iter().next().is_none(), "nothing latched yet");
+
+        event_manager.dispatch_latching(&ConnectionStatusChanged { online: true });
+        event_manager.dispatch_latching(&ConnectionStatusChanged { online: false });
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![("early", true), ("early", false)]);
+
+        let tx_late = tx.clone();
+        event_manager.subscribe_latching(move |event: &ConnectionStatusChanged| {
+            let _ = tx_late.send(("late", event.online));
+        });
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![("late", false)]);
+
+        event_manager.dispatch(&ConnectionStatusChanged { online: true });
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![("early", true), ("late", true)]);
+
+        event_manager.clear_latched::<ConnectionStatusChanged>();
+        event_manager.subscribe_latching(move |_: &ConnectionStatusChanged| {
+            let _ = tx.send(("cleared", true));
+        });
+        assert!(rx.try_iter().next().is_none());
+    }
+}
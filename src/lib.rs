@@ -1,29 +1,326 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::any::{TypeId, Any};
+use std::cell::{Cell, RefCell};
+use std::hash::Hash;
+use std::rc::Rc;
+use std::sync::mpsc::Receiver;
+use std::time::Instant;
 
-// Type alias for our listeners. They are boxed closures that can be mutated
-// and accept a reference to *any* type that has been boxed.
-type Listener = Box<dyn FnMut(&dyn Any)>;
+// Lets `#[derive(Event)]`, which names `::event_forge`, work inside this crate too.
+extern crate self as event_forge;
+
+mod alias;
+#[cfg(any(test, feature = "async"))]
+mod async_dispatch;
+mod barrier;
+mod builder;
+mod capacity;
+mod chain;
+mod channel;
+mod cloning;
+pub mod closed;
+mod collect;
+mod concurrent;
+mod custom_id;
+mod deadline;
+mod debug;
+mod dedup;
+mod emit;
+mod event;
+mod fallible;
+mod frozen;
+mod gated;
+mod guard;
+mod hooks;
+mod info;
+mod latch;
+mod lifecycle;
+mod once;
+mod ordering;
+mod partial;
+mod phase;
+mod priority;
+mod propagation;
+mod queue;
+mod reducer;
+mod round_robin;
+mod router;
+mod sampling;
+mod slow;
+mod stats;
+mod store;
+mod subscription_builder;
+mod tags;
+mod throttle;
+mod trace;
+mod trait_events;
+mod window;
+mod wiring;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+#[cfg(any(test, feature = "async"))]
+pub use async_dispatch::{block_on, AsyncHandler, CancelToken};
+pub use builder::EventManagerBuilder;
+pub use capacity::{CapacityReport, TypeCapacity};
+pub use chain::{DispatchError, EventEmitter};
+pub use collect::Consumable;
+pub use concurrent::SharedEventManager;
+pub use custom_id::{IdInUse, SubscribeError};
+pub use channel::{Backpressure, BoundedReceiver};
+pub use deadline::DispatchOutcome;
+pub use emit::Emit;
+pub use event::Event;
+pub use event_forge_derive::Event;
+pub use frozen::FrozenEventManager;
+pub use guard::Subscription;
+pub use info::SubscriptionInfo;
+pub use ordering::OrderingError;
+pub use phase::Phase;
+pub use queue::{DrainedQueue, NotConverged, QueuedEvent};
+pub use router::EventRouter;
+pub use slow::SlowListener;
+pub use stats::{FrameStats, TypeStats};
+pub use store::{ListenerBucket, ListenerStore};
+pub use subscription_builder::SubscriptionBuilder;
+pub use throttle::Throttle;
+pub use trace::{DispatchTrace, ListenerTrace};
+pub use trait_events::TraitEventManager;
+pub use wiring::WiringError;
+
+/// Type alias for our listeners. They are boxed closures that can be mutated
+/// and accept a reference to *any* type that has been boxed.
+pub type Listener = Box<dyn FnMut(&dyn Any)>;
+
+/// Identifies a single subscription made on an `EventManager`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SubscriptionId(u64);
+
+// Listeners registered with `subscribe_fn`; `Fn` lets them run from `&self`.
+type SharedListener = Box<dyn Fn(&dyn Any)>;
+
+// Listeners that answer an event with an accept/reject decision.
+type Verdict = Box<dyn FnMut(&dyn Any) -> bool>;
+
+// Emitters push any number of outputs into a shared `Vec<O>`, passed here as `&mut dyn Any`.
+type Emitter = Box<dyn FnMut(&dyn Any, &mut dyn Any)>;
+
+// Rewriting listeners that may change the event in place for the ones after them.
+type Rewriter = Box<dyn FnMut(&mut dyn Any)>;
+
+// Consuming listeners, handed an `Option<E>` slot they may take the event out of.
+type Consumer = Box<dyn FnMut(&mut dyn Any)>;
+
+// Query listeners write their single answer into an `Option<R>` slot, passed as `&mut dyn Any`.
+type Query = Box<dyn FnMut(&dyn Any, &mut dyn Any)>;
+
+// Accumulating listeners update a shared `A`, passed as `&mut dyn Any`, and return false to halt.
+type Accumulator = Box<dyn FnMut(&dyn Any, &mut dyn Any) -> bool>;
+
+// What `dispatch_selected` should do with the next listener.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Run,
+    Skip,
+    // Don't run this listener or any after it.
+    Stop,
+}
+
+// A registered listener together with the id handed out when it was subscribed.
+struct ListenerEntry<L = Listener> {
+    id: SubscriptionId,
+    listener: L,
+    // Set by listeners that remove themselves; retired entries are skipped and pruned on dispatch.
+    retired: Option<Rc<Cell<bool>>>,
+}
+
+impl<L> ListenerEntry<L> {
+    fn is_retired(&self) -> bool {
+        self.retired.as_ref().is_some_and(|retired| retired.get())
+    }
+}
 
 // The central event manager
 pub struct EventManager {
     // Stores listeners keyed by the TypeId of the event they listen to.
-    listeners: HashMap<TypeId, Vec<Listener>>,
+    listeners: Box<dyn ListenerStore>,
+    // Read-only listeners, kept apart so `dispatch_shared` can run them without `&mut self`.
+    shared_listeners: HashMap<TypeId, Vec<ListenerEntry<SharedListener>>>,
+    // `(first, then)` ordering constraints between `listeners` of the same type.
+    run_after: HashMap<TypeId, Vec<(SubscriptionId, SubscriptionId)>>,
+    // Phases of `listeners` that were subscribed with `subscribe_in_phase`.
+    phases: HashMap<TypeId, HashMap<SubscriptionId, Phase>>,
+    // Priorities of `listeners` subscribed with `subscribe_with_priority`.
+    priorities: HashMap<TypeId, HashMap<SubscriptionId, i32>>,
+    // Tag groups of `listeners` subscribed with `subscribe_tagged`, and the order they run in.
+    tags: HashMap<TypeId, HashMap<SubscriptionId, String>>,
+    tag_order: Vec<String>,
+    // Names given to `listeners` with `subscribe_named`.
+    names: HashMap<TypeId, HashMap<SubscriptionId, &'static str>>,
+    // Factories of `listeners` subscribed with `subscribe_cloneable`, re-run by `clone`.
+    factories: HashMap<TypeId, Vec<(SubscriptionId, cloning::ListenerFactory)>>,
+    // The order phases run in.
+    phase_order: Vec<Phase>,
+    // Decision listeners registered with `subscribe_verdict`.
+    verdicts: HashMap<TypeId, Vec<ListenerEntry<Verdict>>>,
+    // Query listeners keyed by (event TypeId, result TypeId).
+    queries: HashMap<(TypeId, TypeId), Vec<ListenerEntry<Query>>>,
+    // Accumulating listeners keyed by (event TypeId, accumulator TypeId).
+    accumulators: HashMap<(TypeId, TypeId), Vec<ListenerEntry<Accumulator>>>,
+    // Rewriting listeners registered with `subscribe_rewriting`.
+    rewriters: HashMap<TypeId, Vec<ListenerEntry<Rewriter>>>,
+    // Consuming listeners registered with `subscribe_consuming`.
+    consumers: HashMap<TypeId, Vec<ListenerEntry<Consumer>>>,
+    // Emitter listeners keyed by (event TypeId, output TypeId).
+    emitters: HashMap<(TypeId, TypeId), Vec<ListenerEntry<Emitter>>>,
+    // Listeners registered with `subscribe_async`, only run by `dispatch_async`.
+    #[cfg(any(test, feature = "async"))]
+    async_listeners: HashMap<TypeId, Vec<ListenerEntry<async_dispatch::AsyncListener>>>,
+    // Async query listeners keyed by (event TypeId, answer TypeId).
+    #[cfg(any(test, feature = "async"))]
+    async_queries: HashMap<(TypeId, TypeId), Vec<ListenerEntry<async_dispatch::AsyncQuery>>>,
+    // The token handed to cancelable async listeners of each type, until `cancel_all`.
+    #[cfg(any(test, feature = "async"))]
+    cancel_tokens: HashMap<TypeId, async_dispatch::CancelToken>,
+    // Names of the event (and result) types seen at subscribe time, for `Debug` output.
+    type_names: HashMap<TypeId, &'static str>,
+    // Hooks fired when a type gains its first or loses its last listener.
+    lifecycle: HashMap<TypeId, lifecycle::LifecycleHooks>,
+    // The last event of each type passed to `dispatch_latching`.
+    latched: HashMap<TypeId, Box<dyn Any>>,
+    // Types whose `dispatch` also reaches other types' listeners, see `alias_event`.
+    aliases: HashMap<TypeId, Vec<alias::Alias>>,
+    // Categories `dispatch_event` drops, see `mute_category`.
+    muted_categories: HashSet<String>,
+    // Events buffered by `queue` until the next `flush`.
+    queued: VecDeque<(TypeId, Box<dyn Any>)>,
+    // Per-type windows for `dispatch_deduped`, present only for opted-in types.
+    dedup: HashMap<TypeId, dedup::DedupWindow>,
+    // Deliver pending `Throttle::Latest` events; each returns false once its listener is gone.
+    throttled: Vec<Box<dyn FnMut(Instant) -> bool>>,
+    // Commit `subscribe_reducer` state; each returns false once its listener is gone.
+    reducers: Vec<Box<dyn FnMut() -> bool>>,
+    // Deliver partial `subscribe_windowed` batches; each returns false once its listener is gone.
+    windows: Vec<Box<dyn FnMut() -> bool>>,
+    // Follow-up events queued by `subscribe_chaining` listeners. Empty until one subscribes;
+    // `merge` adds the other manager's, since its listeners keep writing there.
+    outboxes: Vec<Rc<RefCell<chain::EventEmitter>>>,
+    // Per-type `dispatch_round_robin` cursor: the last listener served and its position.
+    round_robin: HashMap<TypeId, (SubscriptionId, usize)>,
+    // Set by `subscribe_stoppable` listeners to end the current dispatch. Empty until one
+    // subscribes; like `outboxes`, `merge` adds the other manager's.
+    stop_flags: Vec<Rc<Cell<bool>>>,
+    // Errors from fallible listeners, awaiting `take_errors`. Empty until one subscribes;
+    // like `outboxes`, `merge` adds the other manager's.
+    error_sinks: Vec<Rc<RefCell<fallible::ErrorSink>>>,
+    // Random source for `dispatch_sampled`.
+    sample_rng: sampling::SampleRng,
+    // Event types and handlers registered by name for `wire_from_config`.
+    wiring: wiring::WiringRegistry,
+    // Callbacks run around every `dispatch_selected`.
+    dispatch_hooks: hooks::DispatchHooks,
+    // Dispatch statistics; `None` until `enable_stats` is called.
+    stats: Option<stats::StatsCollector>,
+    // The threshold and hook for reporting slow listeners; `None` unless opted in.
+    slow_listeners: Option<slow::SlowListenerMonitor>,
+    // Whether buckets emptied by removals are dropped from `listeners`; see `set_auto_prune`.
+    auto_prune: bool,
+    // The raw value of the next SubscriptionId to hand out.
+    next_id: u64,
+    // Set once `next_id` has run past `u64::MAX`, which only chosen ids can cause; from then
+    // on `allocate_id` skips ids that are still in use.
+    ids_wrapped: bool,
+}
+
+impl Default for EventManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl EventManager {
     pub fn new() -> Self {
+        Self::with_store(HashMap::new())
+    }
+
+    /// Creates a manager that keeps its listeners in `store`, e.g. a `HashMap` with a faster
+    /// hasher or a `BTreeMap`. `new()` uses a `HashMap` with the default hasher.
+    pub fn with_store(store: impl ListenerStore + 'static) -> Self {
         EventManager {
-            listeners: HashMap::new(),
+            listeners: Box::new(store),
+            shared_listeners: HashMap::new(),
+            run_after: HashMap::new(),
+            phases: HashMap::new(),
+            priorities: HashMap::new(),
+            tags: HashMap::new(),
+            tag_order: Vec::new(),
+            names: HashMap::new(),
+            factories: HashMap::new(),
+            phase_order: phase::DEFAULT_PHASE_ORDER.to_vec(),
+            verdicts: HashMap::new(),
+            queries: HashMap::new(),
+            accumulators: HashMap::new(),
+            rewriters: HashMap::new(),
+            consumers: HashMap::new(),
+            emitters: HashMap::new(),
+            #[cfg(any(test, feature = "async"))]
+            async_listeners: HashMap::new(),
+            #[cfg(any(test, feature = "async"))]
+            async_queries: HashMap::new(),
+            #[cfg(any(test, feature = "async"))]
+            cancel_tokens: HashMap::new(),
+            type_names: HashMap::new(),
+            lifecycle: HashMap::new(),
+            latched: HashMap::new(),
+            aliases: HashMap::new(),
+            muted_categories: HashSet::new(),
+            queued: VecDeque::new(),
+            dedup: HashMap::new(),
+            throttled: Vec::new(),
+            reducers: Vec::new(),
+            windows: Vec::new(),
+            outboxes: Vec::new(),
+            round_robin: HashMap::new(),
+            stop_flags: Vec::new(),
+            error_sinks: Vec::new(),
+            sample_rng: sampling::SampleRng::from_entropy(),
+            wiring: wiring::WiringRegistry::default(),
+            dispatch_hooks: hooks::DispatchHooks::default(),
+            stats: None,
+            slow_listeners: None,
+            auto_prune: true,
+            next_id: 0,
+            ids_wrapped: false,
+        }
+    }
+
+    // Hands out an id no listener holds. Ids count up from 0, past any chosen with
+    // `subscribe_with_id`; only if that exhausts the range do they start again from 0,
+    // skipping the ones still in use.
+    fn allocate_id(&mut self) -> SubscriptionId {
+        loop {
+            let id = SubscriptionId(self.next_id);
+            self.advance_next_id(id.0);
+            if !self.ids_wrapped || !self.contains_id(id) {
+                return id;
+            }
+        }
+    }
+
+    // Moves `next_id` past `taken`, wrapping round to 0 at the end of the range.
+    fn advance_next_id(&mut self, taken: u64) {
+        match taken.checked_add(1) {
+            Some(next) => self.next_id = next,
+            None => {
+                self.next_id = 0;
+                self.ids_wrapped = true;
+            }
         }
     }
 
     /// Subscribes a listener closure to a specific event type `E`.
     /// The listener must be 'static (cannot hold non-static references).
-    pub fn subscribe<E: Any + 'static>(&mut self, mut listener: impl FnMut(&E) + 'static) {
-        let type_id = TypeId::of::<E>();
-        let listeners = self.listeners.entry(type_id).or_insert_with(Vec::new);
-
+    pub fn subscribe<E: Any + 'static>(&mut self, mut listener: impl FnMut(&E) + 'static) -> SubscriptionId {
         // Wrap the specific listener `FnMut(&E)` into a generic `FnMut(&dyn Any)`.
         // This boxed listener will attempt to downcast the received `&dyn Any`
         // back to the specific type `&E` it knows how to handle.
@@ -33,21 +330,518 @@ impl EventManager {
             }
         });
 
-        listeners.push(boxed_listener);
+        self.remember_type::<E>();
+        self.push_listener(TypeId::of::<E>(), boxed_listener, None)
+    }
+
+    /// Subscribes every boxed listener yielded by `listeners` to event type `E`, reserving
+    /// room for them up front. Returns their ids in the order they were installed.
+    pub fn extend_with<E, I>(&mut self, listeners: I) -> Vec<SubscriptionId>
+    where
+        E: Any + 'static,
+        I: IntoIterator<Item = Box<dyn FnMut(&E)>>,
+    {
+        let listeners = listeners.into_iter();
+        self.listeners
+            .get_or_default(TypeId::of::<E>())
+            .reserve(listeners.size_hint().0);
+        listeners
+            .map(|mut listener| self.subscribe(move |event: &E| listener(event)))
+            .collect()
+    }
+
+    /// Subscribes a read-only listener to event type `E`.
+    /// These live in a separate pool from `subscribe`'s `FnMut` listeners: `dispatch` runs
+    /// both pools (mutable listeners first), while `dispatch_shared` runs only this one.
+    pub fn subscribe_fn<E: Any + 'static>(&mut self, listener: impl Fn(&E) + 'static) -> SubscriptionId {
+        let id = self.allocate_id();
+        let boxed_listener = Box::new(move |event: &dyn Any| {
+            if let Some(specific_event) = event.downcast_ref::<E>() {
+                listener(specific_event);
+            }
+        });
+
+        self.remember_type::<E>();
+        self.sync_lifecycle(TypeId::of::<E>());
+        self.shared_listeners
+            .entry(TypeId::of::<E>())
+            .or_default()
+            .push(ListenerEntry { id, listener: boxed_listener, retired: None });
+        self.sync_lifecycle(TypeId::of::<E>());
+        id
+    }
+
+    // Registers an already type-erased listener under `type_id`.
+    fn push_listener(&mut self, type_id: TypeId, listener: Listener, retired: Option<Rc<Cell<bool>>>) -> SubscriptionId {
+        let id = self.allocate_id();
+        self.insert_listener(type_id, ListenerEntry { id, listener, retired });
+        id
+    }
+
+    // Registers a listener whose id was allocated up front, e.g. because the closure needs it.
+    fn insert_listener(&mut self, type_id: TypeId, entry: ListenerEntry) {
+        // A dropped guard may have left `type_id` without listeners since the last check; its
+        // `on_last` should fire before the new listener's `on_first`.
+        self.sync_lifecycle(type_id);
+        self.listeners.get_or_default(type_id).push(entry);
+        if self.phases.contains_key(&type_id) || self.priorities.contains_key(&type_id) {
+            // Appending may have landed it after a later phase or a lower priority. The new
+            // listener has no ordering constraints, so this can't introduce a cycle.
+            let _ = self.reorder(type_id);
+        }
+        self.sync_lifecycle(type_id);
     }
 
     /// Dispatches an event to all registered listeners for that event type `E`.
     /// The event itself must be 'static.
     pub fn dispatch<E: Any + 'static>(&mut self, event: &E) {
+        self.dispatch_erased(TypeId::of::<E>(), std::any::type_name::<E>(), event);
+    }
+
+    /// Dispatches `event` like `dispatch`, also handing it to `extra` after every registered
+    /// listener, including those of aliased types, has run and before any chained follow-ups.
+    /// `extra` is never registered, so nothing lingers afterwards; handy for one-off
+    /// assertions or debug output.
+    pub fn dispatch_scoped<E: Any + 'static>(&mut self, event: &E, mut extra: impl FnMut(&E)) {
+        self.dispatch_selected(TypeId::of::<E>(), std::any::type_name::<E>(), event, None);
+        if !self.aliases.is_empty() {
+            self.dispatch_aliases(TypeId::of::<E>(), event, None);
+        }
+        extra(event);
+        self.flush_chained();
+    }
+
+    /// Dispatches `event` like `dispatch`, then calls `on_complete` with the number of
+    /// listeners that actually ran.
+    pub fn dispatch_with_ack<E: Any + 'static>(&mut self, event: &E, on_complete: impl FnOnce(usize)) {
+        let invoked = self.dispatch_erased(TypeId::of::<E>(), std::any::type_name::<E>(), event);
+        on_complete(invoked);
+    }
+
+    /// Dispatches an event to the read-only listeners registered with `subscribe_fn`.
+    /// Only needs `&self`, so several parts of the code can dispatch without an exclusive
+    /// borrow. `FnMut` listeners are not run, and the dispatch is not counted in stats.
+    /// Returns how many listeners were invoked.
+    pub fn dispatch_shared<E: Any + 'static>(&self, event: &E) -> usize {
+        let Some(listeners) = self.shared_listeners.get(&TypeId::of::<E>()) else {
+            return 0;
+        };
+        for entry in listeners {
+            (entry.listener)(event);
+        }
+        listeners.len()
+    }
+
+    /// Dispatches every event already waiting on `rx`, in the order they were sent, and
+    /// returns how many were processed. Never blocks: it stops as soon as the channel is
+    /// empty or disconnected, which makes it suitable for pumping once per frame.
+    pub fn pump_from<E: Any + 'static>(&mut self, rx: &Receiver<E>) -> usize {
+        let mut pumped = 0;
+        while let Ok(event) = rx.try_recv() {
+            self.dispatch(&event);
+            pumped += 1;
+        }
+        pumped
+    }
+
+    /// Subscribes a type-erased listener to the event type `type_id`, for callers that only
+    /// know types at runtime, such as scripting or FFI layers. The listener receives the
+    /// event as `&dyn Any` and must downcast it itself. Otherwise it behaves like one added
+    /// with `subscribe`.
+    pub fn subscribe_dyn(&mut self, type_id: TypeId, listener: impl FnMut(&dyn Any) + 'static) -> SubscriptionId {
+        self.push_listener(type_id, Box::new(listener), None)
+    }
+
+    /// Dispatches `event` like `dispatch`, to the listeners of its runtime type. Pass the
+    /// event itself, e.g. `&*boxed` for a `Box<dyn Any>`: `&boxed` would coerce the box and
+    /// reach listeners of `Box<dyn Any>` instead.
+    pub fn dispatch_dyn(&mut self, event: &dyn Any) {
+        let type_id = event.type_id();
+        self.dispatch_erased(type_id, self.type_name_of(type_id), event);
+    }
+
+    /// Dispatches a batch of boxed events of any types, in order, each to the listeners of
+    /// its runtime type. Returns how many listeners each event reached; events nobody
+    /// listens to are skipped and report 0.
+    pub fn dispatch_mixed(&mut self, events: Vec<Box<dyn Any>>) -> Vec<usize> {
+        events
+            .iter()
+            // `(**event)` is the event itself; `event.type_id()` would be the box's.
+            .map(|event| {
+                let type_id = (**event).type_id();
+                self.dispatch_erased(type_id, self.type_name_of(type_id), &**event)
+            })
+            .collect()
+    }
+
+    // The type-erased core of `dispatch`. Returns how many listeners were invoked.
+    fn dispatch_erased(&mut self, type_id: TypeId, type_name: &'static str, event: &dyn Any) -> usize {
+        let mut invoked = self.dispatch_selected(type_id, type_name, event, None);
+        if !self.aliases.is_empty() {
+            invoked += self.dispatch_aliases(type_id, event, None);
+        }
+        self.flush_chained();
+        invoked
+    }
+
+    // Runs the listeners of `type_id`, asking `select`, when given, about each one before
+    // calling it. `select` receives the listener's position among live listeners (mutable
+    // pool first, then `subscribe_fn` listeners) and its id. Without it every listener runs.
+    // `Step::Stop` ends the current pool; `select` is still asked about the first listener
+    // of the next pool, so it should keep answering `Stop`.
+    // Returns how many listeners were invoked.
+    fn dispatch_selected(
+        &mut self,
+        type_id: TypeId,
+        type_name: &'static str,
+        event: &dyn Any,
+        mut select: Option<&mut dyn FnMut(usize, SubscriptionId) -> Step>,
+    ) -> usize {
+        if let Some(pre) = self.dispatch_hooks.pre.as_mut() {
+            pre(type_id);
+        }
+        let started = self.stats.as_ref().map(|_| Instant::now());
+        let mut invoked = 0;
+        let mut position = 0;
+
+        // Once a `subscribe_stoppable` listener breaks, every later one is stopped too.
+        let mut stoppable;
+        let mut select: Option<&mut dyn FnMut(usize, SubscriptionId) -> Step> = if self.stop_flags.is_empty() {
+            // Shortens the trait object's lifetime to match the other branch.
+            select.map(|select| select as &mut dyn FnMut(usize, SubscriptionId) -> Step)
+        } else {
+            let stop_flags = &self.stop_flags;
+            propagation::reset(stop_flags);
+            stoppable = move |position, id| {
+                if propagation::stopped(stop_flags) {
+                    return Step::Stop;
+                }
+                select.as_mut().map_or(Step::Run, |select| select(position, id))
+            };
+            Some(&mut stoppable)
+        };
+
+        // With a slow-listener threshold set, every listener is timed through `select`.
+        let mut timer = self.slow_listeners.as_ref().map(|monitor| slow::ListenerTimer::new(monitor.threshold));
+        let mut timed;
+        let mut select: Option<&mut dyn FnMut(usize, SubscriptionId) -> Step> = match timer.as_mut() {
+            // Shortens the trait object's lifetime to match the other branch.
+            None => select.map(|select| select as &mut dyn FnMut(usize, SubscriptionId) -> Step),
+            Some(timer) => {
+                timed = move |position, id| timer.time(id, select.as_mut().map_or(Step::Run, |select| select(position, id)));
+                Some(&mut timed)
+            }
+        };
+
+        // Get the listeners for this event type, if any, and call each one.
+        // The listener closure itself handles the downcasting.
+        let mut pruned = Vec::new();
+        if let Some(bucket) = self.listeners.get_mut(&type_id) {
+            invoked += bucket.run(event, &mut position, select.as_deref_mut());
+            pruned = bucket.prune_retired();
+        }
+        if let Some(listeners) = self.shared_listeners.get(&type_id) {
+            for entry in listeners {
+                match select.as_mut().map_or(Step::Run, |select| select(position, entry.id)) {
+                    Step::Run => {
+                        (entry.listener)(event);
+                        invoked += 1;
+                    }
+                    Step::Skip => {}
+                    Step::Stop => break,
+                }
+                position += 1;
+            }
+        }
+        if let Some(timer) = timer {
+            self.report_slow(type_id, type_name, timer.finish());
+        }
+        if !pruned.is_empty() {
+            self.prune_empty(type_id);
+            self.forget_listeners(type_id, &pruned);
+            self.sync_lifecycle(type_id);
+        }
+
+        if let (Some(stats), Some(started)) = (self.stats.as_mut(), started) {
+            stats.record(type_id, type_name, invoked, started.elapsed());
+        }
+        if let Some(post) = self.dispatch_hooks.post.as_mut() {
+            post(type_id, invoked);
+        }
+        invoked
+    }
+
+    /// Moves every listener registered on `other`, including verdicts, queries, accumulators,
+    /// rewriters, consumers, emitters and async listeners, into this manager. For each event
+    /// type, `other`'s listeners run after the ones already registered here, keeping their
+    /// relative order. Each moved listener gets a fresh id in
+    /// this manager; the returned map translates `other`'s ids to the new ones.
+    /// Anything else `other` accumulated, such as dispatch statistics or lifecycle hooks,
+    /// is dropped.
+    pub fn merge(&mut self, mut other: EventManager) -> HashMap<SubscriptionId, SubscriptionId> {
+        let mut remapped = HashMap::new();
+        for id in other.entry_ids() {
+            let fresh = self.allocate_id();
+            remapped.insert(id, fresh);
+        }
+        for (type_id, bucket) in other.listeners.drain() {
+            let mut moved = Vec::new();
+            move_bucket(&mut moved, bucket.into_entries(), &remapped);
+            self.listeners.get_or_default(type_id).extend(moved);
+        }
+        move_entries(&mut self.shared_listeners, other.shared_listeners, &remapped);
+        move_entries(&mut self.verdicts, other.verdicts, &remapped);
+        move_entries(&mut self.queries, other.queries, &remapped);
+        move_entries(&mut self.accumulators, other.accumulators, &remapped);
+        move_entries(&mut self.rewriters, other.rewriters, &remapped);
+        move_entries(&mut self.consumers, other.consumers, &remapped);
+        move_entries(&mut self.emitters, other.emitters, &remapped);
+        #[cfg(any(test, feature = "async"))]
+        move_entries(&mut self.async_listeners, other.async_listeners, &remapped);
+        #[cfg(any(test, feature = "async"))]
+        move_entries(&mut self.async_queries, other.async_queries, &remapped);
+        // Ordering constraints only ever relate listeners of the same manager,
+        // so `other`'s carry over unchanged apart from the new ids. Metadata of listeners
+        // that are already gone is skipped, like stale factories below.
+        for (type_id, constraints) in other.run_after {
+            let translated = constraints.into_iter().filter_map(|(first, then)| Some((*remapped.get(&first)?, *remapped.get(&then)?)));
+            self.run_after.entry(type_id).or_default().extend(translated);
+        }
+        for (type_id, phases) in other.phases {
+            let translated = phases.into_iter().filter_map(|(id, phase)| Some((*remapped.get(&id)?, phase)));
+            self.phases.entry(type_id).or_default().extend(translated);
+            // Appended listeners may belong to earlier phases than the ones already here.
+            let _ = self.reorder(type_id);
+        }
+        for (type_id, priorities) in other.priorities {
+            let translated = priorities.into_iter().filter_map(|(id, priority)| Some((*remapped.get(&id)?, priority)));
+            self.priorities.entry(type_id).or_default().extend(translated);
+            let _ = self.reorder(type_id);
+        }
+        for (type_id, tags) in other.tags {
+            let translated = tags.into_iter().filter_map(|(id, tag)| Some((*remapped.get(&id)?, tag)));
+            self.tags.entry(type_id).or_default().extend(translated);
+            let _ = self.reorder(type_id);
+        }
+        for (type_id, names) in other.names {
+            let translated = names.into_iter().filter_map(|(id, name)| Some((*remapped.get(&id)?, name)));
+            self.names.entry(type_id).or_default().extend(translated);
+        }
+        for (type_id, factories) in other.factories {
+            // Factories can outlive their listener until the next `subscribe_cloneable`.
+            let translated = factories.into_iter().filter_map(|(id, factory)| Some((*remapped.get(&id)?, factory)));
+            self.factories.entry(type_id).or_default().extend(translated);
+        }
+        self.throttled.append(&mut other.throttled);
+        self.reducers.append(&mut other.reducers);
+        self.windows.append(&mut other.windows);
+        self.outboxes.append(&mut other.outboxes);
+        self.stop_flags.append(&mut other.stop_flags);
+        self.error_sinks.append(&mut other.error_sinks);
+        for (type_id, name) in other.type_names {
+            self.type_names.entry(type_id).or_insert(name);
+        }
+        let hooked: Vec<TypeId> = self.lifecycle.keys().copied().collect();
+        for type_id in hooked {
+            self.sync_lifecycle(type_id);
+        }
+        remapped
+    }
+
+    /// Removes every listener for event type `E` and hands back the boxed closures with their
+    /// ids, in dispatch order, so they can be inspected or reinstalled elsewhere.
+    /// Read-only `subscribe_fn` listeners are included after the mutable ones.
+    /// Only the closure and id survive: listeners that would have removed themselves
+    /// (such as those installed by `wait_for_all`) lose that ability once drained.
+    pub fn drain_type<E: Any + 'static>(&mut self) -> Vec<(SubscriptionId, Listener)> {
         let type_id = TypeId::of::<E>();
-        // Get the list of listeners for this event type, if any.
-        if let Some(listeners) = self.listeners.get_mut(&type_id) {
-            // Iterate through the listeners and call each one.
-            // The listener closure itself handles the downcasting.
-            for listener in listeners {
-                listener(event);
+        self.run_after.remove(&type_id);
+        self.phases.remove(&type_id);
+        self.priorities.remove(&type_id);
+        self.tags.remove(&type_id);
+        self.names.remove(&type_id);
+        self.factories.remove(&type_id);
+        let mut drained: Vec<(SubscriptionId, Listener)> = self
+            .listeners
+            .remove(&type_id)
+            .unwrap_or_default()
+            .into_entries()
+            .into_iter()
+            .filter(|entry| !entry.is_retired())
+            .map(|entry| (entry.id, entry.listener))
+            .collect();
+        for entry in self.shared_listeners.remove(&type_id).unwrap_or_default() {
+            let listener = entry.listener;
+            drained.push((entry.id, Box::new(move |event: &dyn Any| listener(event))));
+        }
+        self.sync_lifecycle(type_id);
+        drained
+    }
+
+    /// Removes the listener `id`, whichever kind it is. Returns false if no such listener
+    /// exists, for instance because it was already removed or removed itself.
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+        let found = self.listeners.iter().find(|(_, bucket)| bucket.contains_live(id)).map(|(type_id, _)| *type_id);
+        let type_id = if let Some(type_id) = found {
+            if let Some(bucket) = self.listeners.get_mut(&type_id) {
+                bucket.remove(id);
             }
+            self.prune_empty(type_id);
+            type_id
+        } else {
+            let prune = self.auto_prune;
+            let removed = remove_entry(&mut self.shared_listeners, id, prune)
+                .or_else(|| remove_entry(&mut self.verdicts, id, prune))
+                .or_else(|| remove_entry(&mut self.queries, id, prune).map(|(event, _)| event))
+                .or_else(|| remove_entry(&mut self.accumulators, id, prune).map(|(event, _)| event))
+                .or_else(|| remove_entry(&mut self.rewriters, id, prune))
+                .or_else(|| remove_entry(&mut self.consumers, id, prune))
+                .or_else(|| remove_entry(&mut self.emitters, id, prune).map(|(event, _)| event));
+            #[cfg(any(test, feature = "async"))]
+            let removed = removed
+                .or_else(|| remove_entry(&mut self.async_listeners, id, prune))
+                .or_else(|| remove_entry(&mut self.async_queries, id, prune).map(|(event, _)| event));
+            let Some(type_id) = removed else {
+                return false;
+            };
+            type_id
+        };
+
+        self.forget_listeners(type_id, &[id]);
+        self.sync_lifecycle(type_id);
+        true
+    }
+
+    // Drops the ordering constraints, phases, priorities, tags and names recorded for the
+    // removed listeners `ids` of `type_id`.
+    fn forget_listeners(&mut self, type_id: TypeId, ids: &[SubscriptionId]) {
+        if let Some(constraints) = self.run_after.get_mut(&type_id) {
+            constraints.retain(|(first, then)| !ids.contains(first) && !ids.contains(then));
+        }
+        if let Some(phases) = self.phases.get_mut(&type_id) {
+            phases.retain(|id, _| !ids.contains(id));
+        }
+        if let Some(priorities) = self.priorities.get_mut(&type_id) {
+            priorities.retain(|id, _| !ids.contains(id));
+        }
+        if let Some(tags) = self.tags.get_mut(&type_id) {
+            tags.retain(|id, _| !ids.contains(id));
+        }
+        if let Some(names) = self.names.get_mut(&type_id) {
+            names.retain(|id, _| !ids.contains(id));
+        }
+    }
+
+    /// Sets whether removing the last listener of a type also drops the type's now empty
+    /// bucket, after `unsubscribe` and once self-removing listeners are pruned. On by default,
+    /// which keeps memory bounded. Turning it off saves re-creating buckets for types whose
+    /// listeners churn; empty buckets may then remain, though they never count towards
+    /// `type_count` or `is_empty`.
+    pub fn set_auto_prune(&mut self, enabled: bool) {
+        self.auto_prune = enabled;
+    }
+
+    fn prune_empty(&mut self, type_id: TypeId) {
+        if self.auto_prune && self.listeners.get(&type_id).is_some_and(ListenerBucket::is_empty) {
+            self.listeners.remove(&type_id);
+        }
+    }
+
+    /// Returns the ids of the `subscribe` and `subscribe_fn` listeners of `E`, in the order
+    /// `dispatch` runs them: mutable listeners in phase and ordering-constraint order, then
+    /// the read-only ones.
+    pub fn listener_ids<E: Any + 'static>(&self) -> Vec<SubscriptionId> {
+        let type_id = TypeId::of::<E>();
+        let mut ids: Vec<SubscriptionId> = self.listeners.get(&type_id).map(|bucket| bucket.live_ids().collect()).unwrap_or_default();
+        if let Some(shared) = self.shared_listeners.get(&type_id) {
+            ids.extend(shared.iter().map(|entry| entry.id));
+        }
+        ids
+    }
+
+    /// Returns how many `subscribe` and `subscribe_fn` listeners `E` has.
+    pub fn listener_count<E: Any + 'static>(&self) -> usize {
+        self.live_listener_count(TypeId::of::<E>())
+    }
+
+    /// Returns true if `E` has at least one `subscribe` or `subscribe_fn` listener.
+    pub fn has_listeners<E: Any + 'static>(&self) -> bool {
+        self.live_listener_count(TypeId::of::<E>()) > 0
+    }
+
+    /// Dispatches `event` only if `G` has listeners, e.g. to emit debug events only while
+    /// a debug overlay listening to its own event type is attached. Returns whether it did.
+    pub fn dispatch_if_subscribed<E: Any + 'static, G: Any + 'static>(&mut self, event: &E) -> bool {
+        if !self.has_listeners::<G>() {
+            return false;
         }
+        self.dispatch(event);
+        true
+    }
+
+    /// Returns true when no listener of any kind is registered for any event type.
+    /// Buckets left empty after their listeners were removed don't count, so this is true
+    /// after every listener has been drained or has removed itself.
+    pub fn is_empty(&self) -> bool {
+        self.type_count() == 0
+    }
+
+    /// Returns how many distinct event types have at least one listener of any kind
+    /// (`subscribe`, `subscribe_fn`, verdicts, queries, accumulators, rewriters, consumers,
+    /// emitters and async listeners alike). Empty buckets are not counted.
+    pub fn type_count(&self) -> usize {
+        let mut types: HashSet<TypeId> = self
+            .listeners
+            .iter()
+            .filter(|(_, bucket)| bucket.live_count() > 0)
+            .map(|(type_id, _)| *type_id)
+            .collect();
+        types.extend(non_empty_keys(&self.shared_listeners));
+        types.extend(non_empty_keys(&self.verdicts));
+        types.extend(non_empty_keys(&self.queries).map(|(event, _)| event));
+        types.extend(non_empty_keys(&self.accumulators).map(|(event, _)| event));
+        types.extend(non_empty_keys(&self.rewriters));
+        types.extend(non_empty_keys(&self.consumers));
+        types.extend(non_empty_keys(&self.emitters).map(|(event, _)| event));
+        #[cfg(any(test, feature = "async"))]
+        types.extend(non_empty_keys(&self.async_listeners));
+        #[cfg(any(test, feature = "async"))]
+        types.extend(non_empty_keys(&self.async_queries).map(|(event, _)| event));
+        types.len()
+    }
+}
+
+// Removes the listener `id` from `pool`, dropping its key if that left it empty and `prune`
+// is set. Returns the key it was found under.
+fn remove_entry<K: Copy + Eq + Hash, L>(pool: &mut HashMap<K, Vec<ListenerEntry<L>>>, id: SubscriptionId, prune: bool) -> Option<K> {
+    let (&key, entries) = pool.iter_mut().find(|(_, entries)| entries.iter().any(|entry| entry.id == id))?;
+    entries.retain(|entry| entry.id != id);
+    if prune && entries.is_empty() {
+        pool.remove(&key);
+    }
+    Some(key)
+}
+
+// The keys of `pool` that still hold entries.
+fn non_empty_keys<K: Copy, L>(pool: &HashMap<K, Vec<ListenerEntry<L>>>) -> impl Iterator<Item = K> + '_ {
+    pool.iter().filter(|(_, entries)| !entries.is_empty()).map(|(key, _)| *key)
+}
+
+// Appends every entry of `from` to the matching bucket of `into`, under its id from `remapped`.
+fn move_entries<K: Hash + Eq, L>(
+    into: &mut HashMap<K, Vec<ListenerEntry<L>>>,
+    from: HashMap<K, Vec<ListenerEntry<L>>>,
+    remapped: &HashMap<SubscriptionId, SubscriptionId>,
+) {
+    for (key, entries) in from {
+        move_bucket(into.entry(key).or_default(), entries, remapped);
+    }
+}
+
+// Appends `from` to `into`, giving each moved entry its id from `remapped`.
+fn move_bucket<L>(into: &mut Vec<ListenerEntry<L>>, from: Vec<ListenerEntry<L>>, remapped: &HashMap<SubscriptionId, SubscriptionId>) {
+    for entry in from {
+        into.push(ListenerEntry { id: remapped[&entry.id], ..entry });
     }
 }
 
@@ -92,6 +886,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::vec_init_then_push)]
     fn test_event_manager() {
         let mut event_manager = EventManager::new();
 
@@ -151,4 +946,311 @@ mod tests {
         assert!(rx_jump.try_recv().is_err(), "Should be no more jump events");
         assert!(rx_spawn.try_recv().is_err(), "Should be no more spawn events");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn merge_appends_listeners_and_remaps_ids() {
+        let mut host = EventManager::new();
+        let mut plugin = EventManager::new();
+
+        let (tx, rx) = mpsc::channel::<&'static str>();
+
+        let tx_host = tx.clone();
+        host.subscribe(move |_: &PlayerJumped| {
+            let _ = tx_host.send("host");
+        });
+
+        let tx_plugin = tx.clone();
+        let plugin_jump = plugin.subscribe(move |_: &PlayerJumped| {
+            let _ = tx_plugin.send("plugin jump");
+        });
+        let tx_spawn = tx.clone();
+        let plugin_spawn = plugin.subscribe(move |_: &EnemySpawned| {
+            let _ = tx_spawn.send("plugin spawn");
+        });
+
+        let remapped = host.merge(plugin);
+        assert_eq!(remapped.len(), 2);
+        assert_ne!(remapped[&plugin_jump], remapped[&plugin_spawn]);
+
+        host.dispatch(&PlayerJumped { player_id: 1, height: 1.0 });
+        host.dispatch(&EnemySpawned { enemy_type: "Orc".to_string(), position: (0.0, 0.0) });
+
+        let received: Vec<_> = rx.try_iter().collect();
+        assert_eq!(received, vec!["host", "plugin jump", "plugin spawn"]);
+    }
+
+    #[test]
+    fn drain_type_returns_listeners_in_order() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<u32>();
+
+        let tx1 = tx.clone();
+        let first = event_manager.subscribe(move |event: &PlayerJumped| {
+            let _ = tx1.send(event.player_id);
+        });
+        let tx2 = tx.clone();
+        let second = event_manager.subscribe(move |event: &PlayerJumped| {
+            let _ = tx2.send(event.player_id * 10);
+        });
+        event_manager.subscribe(|_: &EnemySpawned| {});
+
+        let drained = event_manager.drain_type::<PlayerJumped>();
+        let ids: Vec<SubscriptionId> = drained.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, vec![first, second]);
+
+        // Nothing is left to receive the event.
+        event_manager.dispatch(&PlayerJumped { player_id: 1, height: 1.0 });
+        assert!(rx.try_recv().is_err());
+
+        // The drained closures are still usable.
+        let event = PlayerJumped { player_id: 2, height: 1.0 };
+        for (_, mut listener) in drained {
+            listener(&event);
+        }
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![2, 20]);
+    }
+
+    #[test]
+    fn dispatch_with_ack_reports_listener_count() {
+        let mut event_manager = EventManager::new();
+        event_manager.subscribe(|_: &PlayerJumped| {});
+        event_manager.subscribe(|_: &PlayerJumped| {});
+
+        let (tx, rx) = mpsc::channel::<usize>();
+        let jump = PlayerJumped { player_id: 1, height: 2.0 };
+        event_manager.dispatch_with_ack(&jump, |count| {
+            let _ = tx.send(count);
+        });
+        assert_eq!(rx.try_recv(), Ok(2));
+
+        let spawn = EnemySpawned { enemy_type: "Bat".to_string(), position: (0.0, 0.0) };
+        event_manager.dispatch_with_ack(&spawn, |count| assert_eq!(count, 0));
+    }
+
+    #[test]
+    fn dispatch_shared_runs_read_only_listeners() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<&'static str>();
+
+        let tx_mut = tx.clone();
+        event_manager.subscribe(move |_: &PlayerJumped| {
+            let _ = tx_mut.send("mutable");
+        });
+        let tx_fn = tx.clone();
+        event_manager.subscribe_fn(move |_: &PlayerJumped| {
+            let _ = tx_fn.send("read-only");
+        });
+
+        // Two shared borrows can dispatch side by side.
+        let jump = PlayerJumped { player_id: 1, height: 1.0 };
+        let (first, second) = (&event_manager, &event_manager);
+        assert_eq!(first.dispatch_shared(&jump), 1);
+        assert_eq!(second.dispatch_shared(&jump), 1);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["read-only", "read-only"]);
+
+        // A regular dispatch reaches both pools.
+        event_manager.dispatch(&jump);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["mutable", "read-only"]);
+    }
+
+    #[test]
+    fn extend_with_installs_many_listeners() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<usize>();
+
+        let listeners = (0..1000).map(|index| {
+            let tx = tx.clone();
+            Box::new(move |_: &PlayerJumped| {
+                let _ = tx.send(index);
+            }) as Box<dyn FnMut(&PlayerJumped)>
+        });
+        let ids = event_manager.extend_with(listeners);
+        assert_eq!(ids.len(), 1000);
+
+        event_manager.dispatch(&PlayerJumped { player_id: 1, height: 1.0 });
+        let received: Vec<usize> = rx.try_iter().collect();
+        assert_eq!(received, (0..1000).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn pump_from_dispatches_events_sent_from_another_thread() {
+        let mut event_manager = EventManager::new();
+        let (tx_seen, rx_seen) = mpsc::channel::<u32>();
+        event_manager.subscribe(move |event: &PlayerJumped| {
+            let _ = tx_seen.send(event.player_id);
+        });
+
+        let (tx, rx) = mpsc::channel::<PlayerJumped>();
+        let producer = std::thread::spawn(move || {
+            for player_id in 0..5 {
+                tx.send(PlayerJumped { player_id, height: 1.0 }).unwrap();
+            }
+        });
+        producer.join().unwrap();
+
+        assert_eq!(event_manager.pump_from(&rx), 5);
+        assert_eq!(rx_seen.try_iter().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+        assert_eq!(event_manager.pump_from(&rx), 0, "Nothing left to pump");
+    }
+
+    #[test]
+    fn is_empty_and_type_count_ignore_empty_buckets() {
+        let mut event_manager = EventManager::new();
+        assert!(event_manager.is_empty());
+
+        event_manager.subscribe(|_: &PlayerJumped| {});
+        event_manager.subscribe_fn(|_: &PlayerJumped| {});
+        event_manager.subscribe_verdict(|_: &EnemySpawned| true);
+        event_manager.subscribe_query(|_: &u32| 1u8);
+        assert_eq!(event_manager.type_count(), 3);
+        assert!(!event_manager.is_empty());
+
+        event_manager.drain_type::<PlayerJumped>();
+        assert_eq!(event_manager.type_count(), 2);
+
+        // A self-removed listener leaves an empty bucket behind, which doesn't count.
+        let mut cleaned_up = EventManager::new();
+        cleaned_up.wait_for_all(&[TypeId::of::<PlayerJumped>()], || {});
+        assert_eq!(cleaned_up.type_count(), 1);
+        cleaned_up.dispatch(&PlayerJumped { player_id: 1, height: 1.0 });
+        assert!(cleaned_up.is_empty());
+    }
+
+    #[test]
+    fn dispatch_mixed_routes_by_runtime_type() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<String>();
+
+        let tx_jump = tx.clone();
+        event_manager.subscribe(move |event: &PlayerJumped| {
+            let _ = tx_jump.send(format!("jump {}", event.player_id));
+        });
+        let tx_spawn = tx.clone();
+        event_manager.subscribe(move |event: &EnemySpawned| {
+            let _ = tx_spawn.send(format!("spawn {}", event.enemy_type));
+        });
+
+        let batch: Vec<Box<dyn Any>> = vec![
+            Box::new(EnemySpawned { enemy_type: "Slime".to_string(), position: (0.0, 0.0) }),
+            Box::new(42u32),
+            Box::new(PlayerJumped { player_id: 3, height: 1.0 }),
+        ];
+        assert_eq!(event_manager.dispatch_mixed(batch), vec![1, 0, 1]);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["spawn Slime", "jump 3"]);
+    }
+
+    #[test]
+    fn dyn_listeners_subscribe_by_type_id() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+
+        event_manager.subscribe_dyn(TypeId::of::<PlayerJumped>(), move |event| {
+            if let Some(jump) = event.downcast_ref::<PlayerJumped>() {
+                let _ = tx.send(jump.player_id);
+            }
+        });
+
+        let boxed: Box<dyn Any> = Box::new(PlayerJumped { player_id: 7, height: 2.0 });
+        event_manager.dispatch_dyn(&*boxed);
+        event_manager.dispatch(&PlayerJumped { player_id: 8, height: 1.0 });
+        event_manager.dispatch_dyn(&EnemySpawned { enemy_type: "Bat".to_string(), position: (1.0, 1.0) });
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![7, 8]);
+    }
+
+    #[test]
+    fn listener_ids_follow_dispatch_order() {
+        let mut event_manager = EventManager::new();
+        let read_only = event_manager.subscribe_fn(|_: &PlayerJumped| {});
+        let first = event_manager.subscribe(|_: &PlayerJumped| {});
+        let late = event_manager.subscribe_in_phase(Phase::PostUpdate, |_: &PlayerJumped| {});
+        let early = event_manager.subscribe_in_phase(Phase::PreUpdate, |_: &PlayerJumped| {});
+        event_manager.subscribe(|_: &EnemySpawned| {});
+
+        assert_eq!(event_manager.listener_ids::<PlayerJumped>(), vec![early, first, late, read_only]);
+        assert!(event_manager.listener_ids::<u32>().is_empty());
+    }
+
+    #[test]
+    fn unsubscribe_removes_any_kind_of_listener() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<&'static str>();
+
+        let tx_jump = tx.clone();
+        let jump = event_manager.subscribe(move |_: &PlayerJumped| {
+            let _ = tx_jump.send("jump");
+        });
+        let tx_shared = tx.clone();
+        let shared = event_manager.subscribe_fn(move |_: &PlayerJumped| {
+            let _ = tx_shared.send("shared");
+        });
+        let verdict = event_manager.subscribe_verdict(|_: &EnemySpawned| false);
+
+        assert!(event_manager.unsubscribe(jump));
+        assert!(!event_manager.unsubscribe(jump));
+        event_manager.dispatch(&PlayerJumped { player_id: 1, height: 1.0 });
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["shared"]);
+
+        assert!(event_manager.unsubscribe(shared));
+        assert!(event_manager.unsubscribe(verdict));
+        assert!(event_manager.is_empty());
+    }
+
+    #[test]
+    fn auto_prune_drops_emptied_buckets() {
+        let mut event_manager = EventManager::new();
+        let jump = event_manager.subscribe(|_: &PlayerJumped| {});
+        event_manager.unsubscribe(jump);
+        assert!(event_manager.listeners.get(&TypeId::of::<PlayerJumped>()).is_none());
+
+        // Self-removing listeners are pruned the same way.
+        event_manager.wait_for_all(&[TypeId::of::<EnemySpawned>()], || {});
+        event_manager.dispatch(&EnemySpawned { enemy_type: "orc".to_string(), position: (0.0, 0.0) });
+        assert!(event_manager.listeners.get(&TypeId::of::<EnemySpawned>()).is_none());
+
+        event_manager.set_auto_prune(false);
+        let jump = event_manager.subscribe(|_: &PlayerJumped| {});
+        event_manager.unsubscribe(jump);
+        assert!(event_manager.listeners.get(&TypeId::of::<PlayerJumped>()).is_some_and(ListenerBucket::is_empty));
+        assert!(event_manager.is_empty());
+    }
+
+    #[test]
+    fn dispatch_scoped_runs_extra_last_without_registering_it() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<u32>();
+        let tx_listener = tx.clone();
+        event_manager.subscribe(move |event: &PlayerJumped| {
+            let _ = tx_listener.send(event.player_id);
+        });
+
+        event_manager.dispatch_scoped(&PlayerJumped { player_id: 3, height: 1.0 }, |event| {
+            let _ = tx.send(event.player_id * 100);
+        });
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![3, 300]);
+
+        event_manager.dispatch(&PlayerJumped { player_id: 4, height: 1.0 });
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![4]);
+        assert_eq!(event_manager.listener_ids::<PlayerJumped>().len(), 1);
+    }
+
+    #[test]
+    fn dispatch_if_subscribed_depends_on_the_other_type() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<u32>();
+        event_manager.subscribe(move |event: &PlayerJumped| {
+            let _ = tx.send(event.player_id);
+        });
+
+        assert!(!event_manager.has_listeners::<EnemySpawned>());
+        assert!(!event_manager.dispatch_if_subscribed::<_, EnemySpawned>(&PlayerJumped { player_id: 1, height: 1.0 }));
+        assert!(rx.try_iter().next().is_none());
+
+        let overlay = event_manager.subscribe_fn(|_: &EnemySpawned| {});
+        assert!(event_manager.has_listeners::<EnemySpawned>());
+        assert!(event_manager.dispatch_if_subscribed::<_, EnemySpawned>(&PlayerJumped { player_id: 2, height: 1.0 }));
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![2]);
+
+        event_manager.unsubscribe(overlay);
+        assert!(!event_manager.dispatch_if_subscribed::<_, EnemySpawned>(&PlayerJumped { player_id: 3, height: 1.0 }));
+    }
+}
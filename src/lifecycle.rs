@@ -0,0 +1,105 @@
+use std::any::{Any, TypeId};
+
+use crate::{EventManager, ListenerBucket};
+
+// Hooks for one event type, plus whether `on_first` has fired without a matching `on_last`.
+pub(crate) struct LifecycleHooks {
+    on_first: Box<dyn FnMut()>,
+    on_last: Box<dyn FnMut()>,
+    active: bool,
+}
+
+impl EventManager {
+    /// Registers hooks for event type `E`: `on_first` fires when `E` goes from no listeners
+    /// to one, `on_last` when its last listener is removed. Both `subscribe` and
+    /// `subscribe_fn` listeners count. Registering replaces any previous hooks for `E`;
+    /// neither hook fires for the listeners that already exist at that point. When the last
+    /// listener goes because its `subscribe_guarded` guard was dropped, `on_last` fires on
+    /// the next dispatch of or subscription to `E` instead.
+    pub fn set_lifecycle_hooks<E: Any + 'static>(&mut self, on_first: impl FnMut() + 'static, on_last: impl FnMut() + 'static) {
+        let type_id = TypeId::of::<E>();
+        let active = self.live_listener_count(type_id) > 0;
+        self.lifecycle.insert(
+            type_id,
+            LifecycleHooks {
+                on_first: Box::new(on_first),
+                on_last: Box::new(on_last),
+                active,
+            },
+        );
+    }
+
+    /// Removes the lifecycle hooks for `E`, if any.
+    pub fn clear_lifecycle_hooks<E: Any + 'static>(&mut self) {
+        self.lifecycle.remove(&TypeId::of::<E>());
+    }
+
+    pub(crate) fn live_listener_count(&self, type_id: TypeId) -> usize {
+        let mutable = self
+            .listeners
+            .get(&type_id)
+            .map_or(0, ListenerBucket::live_count);
+        let shared = self.shared_listeners.get(&type_id).map_or(0, Vec::len);
+        mutable + shared
+    }
+
+    // Fires whichever hook matches a change in whether `type_id` has listeners.
+    // Called after every path that adds or removes listeners.
+    pub(crate) fn sync_lifecycle(&mut self, type_id: TypeId) {
+        if !self.lifecycle.contains_key(&type_id) {
+            return;
+        }
+        let has_listeners = self.live_listener_count(type_id) > 0;
+        let Some(hooks) = self.lifecycle.get_mut(&type_id) else {
+            return;
+        };
+        if hooks.active == has_listeners {
+            return;
+        }
+        hooks.active = has_listeners;
+        if has_listeners {
+            (hooks.on_first)();
+        } else {
+            (hooks.on_last)();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    struct SensorReading;
+    struct OtherEvent;
+
+    #[test]
+    fn lifecycle_hooks_fire_on_first_and_last_listener() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<&'static str>();
+
+        let tx_first = tx.clone();
+        let tx_last = tx.clone();
+        event_manager.set_lifecycle_hooks::<SensorReading>(
+            move || {
+                let _ = tx_first.send("start polling");
+            },
+            move || {
+                let _ = tx_last.send("stop polling");
+            },
+        );
+
+        event_manager.subscribe(|_: &SensorReading| {});
+        event_manager.subscribe_fn(|_: &SensorReading| {});
+        event_manager.subscribe(|_: &OtherEvent| {});
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["start polling"]);
+
+        event_manager.drain_type::<SensorReading>();
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["stop polling"]);
+
+        // Self-removing listeners report the transition once they are pruned.
+        event_manager.wait_for_all(&[TypeId::of::<SensorReading>()], || {});
+        event_manager.dispatch(&SensorReading);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["start polling", "stop polling"]);
+    }
+}
@@ -0,0 +1,78 @@
+use std::any::{Any, TypeId};
+use std::cell::Cell;
+use std::rc::Rc;
+
+use crate::{EventManager, SubscriptionId};
+
+impl EventManager {
+    /// Subscribes a listener to `E` that handles only the next `E` dispatched and then
+    /// removes itself. Until then it can be cancelled with `unsubscribe` like any other.
+    pub fn subscribe_once<E: Any + 'static>(&mut self, listener: impl FnOnce(&E) + 'static) -> SubscriptionId {
+        let retired = Rc::new(Cell::new(false));
+        let retired_flag = Rc::clone(&retired);
+        let mut listener = Some(listener);
+        let boxed_listener = Box::new(move |event: &dyn Any| {
+            let Some(specific_event) = event.downcast_ref::<E>() else {
+                return;
+            };
+            if let Some(listener) = listener.take() {
+                retired_flag.set(true);
+                listener(specific_event);
+            }
+        });
+        self.remember_type::<E>();
+        self.push_listener(TypeId::of::<E>(), boxed_listener, Some(retired))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    struct LevelLoaded(u32);
+
+    #[test]
+    fn once_listeners_handle_a_single_event() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+
+        let tx_once = tx.clone();
+        event_manager.subscribe_once(move |event: &LevelLoaded| {
+            let _ = tx_once.send(("once", event.0));
+        });
+        event_manager.subscribe(move |event: &LevelLoaded| {
+            let _ = tx.send(("always", event.0));
+        });
+
+        event_manager.dispatch(&LevelLoaded(1));
+        event_manager.dispatch(&LevelLoaded(2));
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![("once", 1), ("always", 1), ("always", 2)]);
+        assert_eq!(event_manager.listener_count::<LevelLoaded>(), 1);
+
+        let cancelled = event_manager.subscribe_once(|_: &LevelLoaded| panic!("cancelled listeners never run"));
+        assert!(event_manager.unsubscribe(cancelled));
+        event_manager.dispatch(&LevelLoaded(3));
+    }
+
+    #[test]
+    fn fired_once_listeners_leave_no_metadata_behind() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+        let first = event_manager.subscribe_once(|_: &LevelLoaded| {});
+        event_manager
+            .subscribe_after(first, move |event: &LevelLoaded| {
+                let _ = tx.send(event.0);
+            })
+            .unwrap();
+        event_manager.dispatch(&LevelLoaded(1));
+
+        let type_id = TypeId::of::<LevelLoaded>();
+        assert!(event_manager.run_after[&type_id].is_empty());
+
+        let mut host = EventManager::new();
+        host.merge(event_manager);
+        host.dispatch(&LevelLoaded(2));
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![1, 2]);
+    }
+}
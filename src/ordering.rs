@@ -0,0 +1,293 @@
+use std::any::{Any, TypeId};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::phase::phase_rank;
+use crate::tags::tag_rank;
+use crate::{EventManager, Phase, SubscriptionId};
+
+/// Errors from declaring an ordering between listeners.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderingError {
+    /// The referenced id is not a listener of this event type.
+    UnknownSubscription(SubscriptionId),
+    /// The constraint would make listeners wait on each other in a loop,
+    /// so it was rejected and the previous ordering kept.
+    Cycle,
+}
+
+impl fmt::Display for OrderingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderingError::UnknownSubscription(id) => write!(f, "{id:?} is not a listener of this event type"),
+            OrderingError::Cycle => write!(f, "ordering constraints form a cycle"),
+        }
+    }
+}
+
+impl std::error::Error for OrderingError {}
+
+impl EventManager {
+    /// Subscribes a listener to `E` that always runs after the listener `after`.
+    /// `after` must be a `subscribe`d listener of `E`.
+    pub fn subscribe_after<E: Any + 'static>(
+        &mut self,
+        after: SubscriptionId,
+        listener: impl FnMut(&E) + 'static,
+    ) -> Result<SubscriptionId, OrderingError> {
+        let type_id = TypeId::of::<E>();
+        if !self.has_listener(type_id, after) {
+            return Err(OrderingError::UnknownSubscription(after));
+        }
+
+        let id = self.subscribe(listener);
+        self.run_after.entry(type_id).or_default().push((after, id));
+        // A brand new listener has nothing depending on it, so this cannot form a cycle.
+        self.reorder(type_id)?;
+        Ok(id)
+    }
+
+    /// Subscribes a listener to `E` that runs immediately before the listener `before`.
+    /// `before` must be a `subscribe`d listener of `E`; otherwise nothing is subscribed.
+    /// It stays ahead of `before` as other listeners come and go, but later constraints
+    /// may place other listeners between the two.
+    pub fn subscribe_before<E: Any + 'static>(
+        &mut self,
+        before: SubscriptionId,
+        listener: impl FnMut(&E) + 'static,
+    ) -> Result<SubscriptionId, OrderingError> {
+        let type_id = TypeId::of::<E>();
+        if !self.has_listener(type_id, before) {
+            return Err(OrderingError::UnknownSubscription(before));
+        }
+
+        let id = self.subscribe(listener);
+        self.run_after.entry(type_id).or_default().push((id, before));
+        // Nothing else waits on the brand new listener, so this cannot form a cycle either.
+        self.reorder(type_id)?;
+        Ok(id)
+    }
+
+    /// Requires the existing listener `id` to run after the existing listener `after`,
+    /// both listening to `E`. Constraints accumulate, and listeners not bound by any keep
+    /// their subscription order. A constraint that would create a cycle is rejected.
+    pub fn order_after<E: Any + 'static>(&mut self, id: SubscriptionId, after: SubscriptionId) -> Result<(), OrderingError> {
+        let type_id = TypeId::of::<E>();
+        for listener in [id, after] {
+            if !self.has_listener(type_id, listener) {
+                return Err(OrderingError::UnknownSubscription(listener));
+            }
+        }
+
+        self.run_after.entry(type_id).or_default().push((after, id));
+        if let Err(err) = self.reorder(type_id) {
+            if let Some(constraints) = self.run_after.get_mut(&type_id) {
+                constraints.pop();
+            }
+            // Without the offending constraint the previous ordering is valid again.
+            self.reorder(type_id)?;
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    fn has_listener(&self, type_id: TypeId, id: SubscriptionId) -> bool {
+        self.listeners
+            .get(&type_id)
+            .is_some_and(|bucket| bucket.contains_live(id))
+    }
+
+    // Re-sorts the listeners of `type_id`: by phase, priority, tag group, then subscription order,
+    // adjusted so every `(first, then)` constraint has `first` running before `then`.
+    pub(crate) fn reorder(&mut self, type_id: TypeId) -> Result<(), OrderingError> {
+        let Some(bucket) = self.listeners.get_mut(&type_id) else {
+            return Ok(());
+        };
+        let constraints = self.run_after.entry(type_id).or_default();
+
+        let mut listeners = bucket.take_sequenced();
+        let phases = self.phases.get_mut(&type_id);
+        let priorities = self.priorities.get_mut(&type_id);
+        let tags = self.tags.get_mut(&type_id);
+        let phase_of = |id| phases.as_ref().and_then(|phases| phases.get(&id)).copied().unwrap_or(Phase::Update);
+        let priority_of = |id| priorities.as_ref().and_then(|priorities| priorities.get(&id)).copied().unwrap_or(0);
+        let tag_of = |id| tags.as_ref().and_then(|tags| tags.get(&id));
+        listeners.sort_by_key(|(seq, entry)| {
+            (
+                phase_rank(&self.phase_order, phase_of(entry.id)),
+                Reverse(priority_of(entry.id)),
+                tag_rank(&self.tag_order, tag_of(entry.id)),
+                *seq,
+            )
+        });
+        let ids: Vec<SubscriptionId> = listeners.iter().map(|(_, entry)| entry.id).collect();
+        // Constraints, phases, priorities and tags of listeners that have since been removed no longer matter.
+        constraints.retain(|(first, then)| ids.contains(first) && ids.contains(then));
+        if let Some(phases) = phases {
+            phases.retain(|id, _| ids.contains(id));
+        }
+        if let Some(priorities) = priorities {
+            priorities.retain(|id, _| ids.contains(id));
+        }
+        if let Some(tags) = tags {
+            tags.retain(|id, _| ids.contains(id));
+        }
+
+        let order = match topological_order(&ids, constraints) {
+            Ok(order) => order,
+            Err(err) => {
+                bucket.restore(listeners);
+                return Err(err);
+            }
+        };
+        let mut slots: Vec<_> = listeners.into_iter().map(Some).collect();
+        bucket.restore(order.into_iter().filter_map(|index| slots[index].take()));
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mark {
+    Unvisited,
+    Visiting,
+    Done,
+}
+
+// Orders the indices of `ids` so each constraint's `first` precedes its `then`.
+// Nodes are visited in their base order and pull their prerequisites in just ahead of
+// themselves, so unconstrained listeners don't move.
+fn topological_order(ids: &[SubscriptionId], constraints: &[(SubscriptionId, SubscriptionId)]) -> Result<Vec<usize>, OrderingError> {
+    let index: HashMap<SubscriptionId, usize> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+    let mut prerequisites: Vec<Vec<usize>> = vec![Vec::new(); ids.len()];
+    for (first, then) in constraints {
+        if let (Some(&first), Some(&then)) = (index.get(first), index.get(then)) {
+            prerequisites[then].push(first);
+        }
+    }
+    for list in &mut prerequisites {
+        list.sort_unstable();
+    }
+
+    fn visit(node: usize, prerequisites: &[Vec<usize>], marks: &mut [Mark], order: &mut Vec<usize>) -> Result<(), OrderingError> {
+        match marks[node] {
+            Mark::Done => return Ok(()),
+            Mark::Visiting => return Err(OrderingError::Cycle),
+            Mark::Unvisited => {}
+        }
+        marks[node] = Mark::Visiting;
+        for &first in &prerequisites[node] {
+            visit(first, prerequisites, marks, order)?;
+        }
+        marks[node] = Mark::Done;
+        order.push(node);
+        Ok(())
+    }
+
+    let mut marks = vec![Mark::Unvisited; ids.len()];
+    let mut order = Vec::with_capacity(ids.len());
+    for node in 0..ids.len() {
+        visit(node, &prerequisites, &mut marks, &mut order)?;
+    }
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    struct Attack;
+
+    fn recorder(event_manager: &mut EventManager, tx: &mpsc::Sender<&'static str>, name: &'static str) -> SubscriptionId {
+        let tx = tx.clone();
+        event_manager.subscribe(move |_: &Attack| {
+            let _ = tx.send(name);
+        })
+    }
+
+    #[test]
+    fn subscribe_after_honors_chains() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+
+        let log = recorder(&mut event_manager, &tx, "log");
+        let buff = recorder(&mut event_manager, &tx, "apply buff");
+        let tx_damage = tx.clone();
+        let damage = event_manager
+            .subscribe_after(buff, move |_: &Attack| {
+                let _ = tx_damage.send("compute damage");
+            })
+            .unwrap();
+        let tx_hud = tx.clone();
+        let hud = event_manager
+            .subscribe_after(damage, move |_: &Attack| {
+                let _ = tx_hud.send("update hud");
+            })
+            .unwrap();
+
+        event_manager.dispatch(&Attack);
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            vec!["log", "apply buff", "compute damage", "update hud"]
+        );
+
+        // Moving the first listener behind the end of the chain drags nothing else along.
+        event_manager.order_after::<Attack>(log, hud).unwrap();
+        event_manager.dispatch(&Attack);
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            vec!["apply buff", "compute damage", "update hud", "log"]
+        );
+    }
+
+    #[test]
+    fn subscribe_before_inserts_just_ahead() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+
+        recorder(&mut event_manager, &tx, "log");
+        let apply = recorder(&mut event_manager, &tx, "apply");
+        recorder(&mut event_manager, &tx, "animate");
+        let tx_validate = tx.clone();
+        event_manager
+            .subscribe_before(apply, move |_: &Attack| {
+                let _ = tx_validate.send("validate");
+            })
+            .unwrap();
+
+        event_manager.dispatch(&Attack);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["log", "validate", "apply", "animate"]);
+
+        let other = event_manager.subscribe(|_: &u32| {});
+        assert_eq!(
+            event_manager.subscribe_before(other, |_: &Attack| {}),
+            Err(OrderingError::UnknownSubscription(other))
+        );
+    }
+
+    #[test]
+    fn ordering_rejects_cycles_and_unknown_ids() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+
+        let a = recorder(&mut event_manager, &tx, "a");
+        let b = recorder(&mut event_manager, &tx, "b");
+        let c = recorder(&mut event_manager, &tx, "c");
+
+        event_manager.order_after::<Attack>(b, c).unwrap();
+        event_manager.order_after::<Attack>(a, b).unwrap();
+        assert_eq!(event_manager.order_after::<Attack>(c, a), Err(OrderingError::Cycle));
+        assert_eq!(event_manager.order_after::<Attack>(a, a), Err(OrderingError::Cycle));
+
+        // The rejected constraints left the valid ordering intact.
+        event_manager.dispatch(&Attack);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["c", "b", "a"]);
+
+        let other = event_manager.subscribe(|_: &u32| {});
+        assert_eq!(
+            event_manager.subscribe_after(other, |_: &Attack| {}),
+            Err(OrderingError::UnknownSubscription(other))
+        );
+    }
+}
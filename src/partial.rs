@@ -0,0 +1,82 @@
+use std::any::{Any, TypeId};
+use std::ops::Range;
+
+use crate::{EventManager, Step};
+
+impl EventManager {
+    /// Dispatches `event` only to the listeners of `E` whose dispatch position, as given by
+    /// `listener_ids`, falls within `range`, e.g. to spread a large listener set over several
+    /// frames. Both bounds are clamped to `listener_count`, so `0..usize::MAX` reaches every
+    /// listener and an empty or out-of-bounds range reaches none and doesn't dispatch at all.
+    /// Returns how many listeners were invoked.
+    ///
+    /// Positions shift when listeners are added or removed between calls, so a pass over
+    /// consecutive ranges only covers every listener exactly once if the set doesn't change.
+    /// Positions only cover `E`'s own listeners, so aliases set up with `alias_event` aren't
+    /// followed; dispatch the aliased type separately to reach its listeners.
+    pub fn dispatch_range<E: Any + 'static>(&mut self, event: &E, range: Range<usize>) -> usize {
+        let type_id = TypeId::of::<E>();
+        let count = self.live_listener_count(type_id);
+        let (start, end) = (range.start.min(count), range.end.min(count));
+        if start >= end {
+            return 0;
+        }
+
+        let invoked = self.dispatch_selected(
+            type_id,
+            std::any::type_name::<E>(),
+            event,
+            Some(&mut |position, _| match position {
+                position if position < start => Step::Skip,
+                position if position < end => Step::Run,
+                _ => Step::Stop,
+            }),
+        );
+        self.flush_chained();
+        invoked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    struct PathfindingTick;
+
+    #[test]
+    fn consecutive_ranges_reach_each_listener_once() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+
+        for agent in 0..5 {
+            let tx = tx.clone();
+            event_manager.subscribe(move |_: &PathfindingTick| {
+                let _ = tx.send(agent);
+            });
+        }
+        assert_eq!(event_manager.listener_count::<PathfindingTick>(), 5);
+
+        assert_eq!(event_manager.dispatch_range(&PathfindingTick, 0..2), 2);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(event_manager.dispatch_range(&PathfindingTick, 2..5), 3);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![2, 3, 4]);
+
+        // Bounds past the end are clamped.
+        assert_eq!(event_manager.dispatch_range(&PathfindingTick, 4..10), 1);
+        assert_eq!(event_manager.dispatch_range(&PathfindingTick, 7..9), 0);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![4]);
+    }
+
+    struct NavTick;
+
+    #[test]
+    fn ranges_dont_follow_aliases() {
+        let mut event_manager = EventManager::new();
+        event_manager.subscribe(|_: &PathfindingTick| {});
+        event_manager.subscribe(|_: &NavTick| panic!("aliases aren't followed by dispatch_range"));
+        event_manager.alias_event(|_: &PathfindingTick| NavTick);
+
+        assert_eq!(event_manager.dispatch_range(&PathfindingTick, 0..usize::MAX), 1);
+    }
+}
@@ -0,0 +1,97 @@
+use std::any::{Any, TypeId};
+
+use crate::{EventManager, SubscriptionId};
+
+/// A coarse stage of event handling. Within one event type, every listener of a phase runs
+/// before any listener of the next; the order of phases is set with `set_phase_order`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Phase {
+    PreUpdate,
+    /// The phase of listeners subscribed without one.
+    Update,
+    PostUpdate,
+    /// An application-defined phase. Unless placed with `set_phase_order`, custom phases
+    /// run after the built-in ones, lowest number first.
+    Custom(u32),
+}
+
+/// The order phases run in until `set_phase_order` is called.
+pub(crate) const DEFAULT_PHASE_ORDER: [Phase; 3] = [Phase::PreUpdate, Phase::Update, Phase::PostUpdate];
+
+impl EventManager {
+    /// Subscribes a listener to `E` that runs in `phase`. Plain `subscribe` listeners belong
+    /// to `Phase::Update`; read-only `subscribe_fn` listeners still run after all phases.
+    pub fn subscribe_in_phase<E: Any + 'static>(&mut self, phase: Phase, listener: impl FnMut(&E) + 'static) -> SubscriptionId {
+        let type_id = TypeId::of::<E>();
+        let id = self.subscribe(listener);
+        self.phases.entry(type_id).or_default().insert(id, phase);
+        // The new listener has no ordering constraints, so this can't introduce a cycle.
+        let _ = self.reorder(type_id);
+        id
+    }
+
+    /// Sets the order phases run in for every event type. Phases missing from `order` run
+    /// after the listed ones, in `Phase`'s declaration order; repeated phases keep their
+    /// first position. `subscribe_after`/`order_after` constraints take precedence over
+    /// phases, so a constraint across phases pulls its listener out of its phase.
+    pub fn set_phase_order(&mut self, order: &[Phase]) {
+        self.phase_order = order.to_vec();
+        let phased: Vec<TypeId> = self.phases.keys().copied().collect();
+        for type_id in phased {
+            // Only the base order changed; the constraints are as acyclic as before.
+            let _ = self.reorder(type_id);
+        }
+    }
+}
+
+// Sort key placing `phase` according to `order`.
+pub(crate) fn phase_rank(order: &[Phase], phase: Phase) -> (usize, Option<Phase>) {
+    match order.iter().position(|listed| *listed == phase) {
+        Some(index) => (index, None),
+        None => (order.len(), Some(phase)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    struct FrameTick;
+
+    fn recorder(event_manager: &mut EventManager, tx: &mpsc::Sender<&'static str>, phase: Phase, name: &'static str) {
+        let tx = tx.clone();
+        event_manager.subscribe_in_phase(phase, move |_: &FrameTick| {
+            let _ = tx.send(name);
+        });
+    }
+
+    #[test]
+    fn phases_run_in_order_with_their_listeners_grouped() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+
+        recorder(&mut event_manager, &tx, Phase::Update, "move");
+        recorder(&mut event_manager, &tx, Phase::PostUpdate, "render");
+        recorder(&mut event_manager, &tx, Phase::Custom(7), "telemetry");
+        recorder(&mut event_manager, &tx, Phase::PreUpdate, "input");
+        recorder(&mut event_manager, &tx, Phase::Update, "collide");
+        let tx_plain = tx.clone();
+        event_manager.subscribe(move |_: &FrameTick| {
+            let _ = tx_plain.send("animate");
+        });
+
+        event_manager.dispatch(&FrameTick);
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            vec!["input", "move", "collide", "animate", "render", "telemetry"]
+        );
+
+        event_manager.set_phase_order(&[Phase::Custom(7), Phase::PostUpdate, Phase::Update]);
+        event_manager.dispatch(&FrameTick);
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            vec!["telemetry", "render", "move", "collide", "animate", "input"]
+        );
+    }
+}
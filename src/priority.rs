@@ -0,0 +1,59 @@
+use std::any::{Any, TypeId};
+
+use crate::{EventManager, SubscriptionId};
+
+impl EventManager {
+    /// Subscribes a listener to `E` with `priority`. Within a phase, listeners run from the
+    /// highest priority to the lowest, ahead of tag groups; listeners sharing a priority keep
+    /// their subscription order. Plain `subscribe` listeners have priority 0, so a positive
+    /// priority runs before them, e.g. for logging or validation, and a negative one after.
+    /// `subscribe_after`/`order_after` constraints still take precedence.
+    pub fn subscribe_with_priority<E: Any + 'static>(&mut self, priority: i32, listener: impl FnMut(&E) + 'static) -> SubscriptionId {
+        let type_id = TypeId::of::<E>();
+        let id = self.subscribe(listener);
+        self.priorities.entry(type_id).or_default().insert(id, priority);
+        // The new listener has no ordering constraints, so this can't introduce a cycle.
+        let _ = self.reorder(type_id);
+        id
+    }
+
+    // The priority of the listener `id` of `type_id`.
+    pub(crate) fn priority_of(&self, type_id: TypeId, id: SubscriptionId) -> i32 {
+        self.priorities.get(&type_id).and_then(|priorities| priorities.get(&id)).copied().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Phase;
+    use std::sync::mpsc;
+
+    struct AttackLanded;
+
+    #[test]
+    fn higher_priorities_run_first() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+        let listener = |name: &'static str| {
+            let tx = tx.clone();
+            move |_: &AttackLanded| {
+                let _ = tx.send(name);
+            }
+        };
+
+        event_manager.subscribe(listener("gameplay"));
+        event_manager.subscribe_with_priority(-5, listener("effects"));
+        event_manager.subscribe_with_priority(10, listener("validation"));
+        event_manager.subscribe_with_priority(20, listener("logging"));
+        event_manager.subscribe_with_priority(10, listener("anti-cheat"));
+        event_manager.subscribe(listener("score"));
+        event_manager.subscribe_in_phase(Phase::PreUpdate, listener("input"));
+
+        event_manager.dispatch(&AttackLanded);
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            vec!["input", "logging", "validation", "anti-cheat", "gameplay", "score", "effects"]
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use std::any::{Any, TypeId};
+use std::cell::Cell;
+use std::ops::ControlFlow;
+use std::rc::Rc;
+
+use crate::{EventManager, SubscriptionId};
+
+impl EventManager {
+    /// Subscribes a listener to `E` that can stop the event from reaching the listeners
+    /// after it by returning `ControlFlow::Break`, e.g. a UI layer consuming a click before
+    /// gameplay sees it. Combine it with `subscribe_with_priority` or phases to run it early
+    /// enough. Stopping ends the current dispatch of `E` only: aliased types and chained
+    /// follow-ups are still dispatched, and the next `E` reaches every listener again.
+    pub fn subscribe_stoppable<E: Any + 'static>(&mut self, mut listener: impl FnMut(&E) -> ControlFlow<()> + 'static) -> SubscriptionId {
+        if self.stop_flags.is_empty() {
+            self.stop_flags.push(Rc::default());
+        }
+        let stopped = Rc::clone(&self.stop_flags[0]);
+        let boxed_listener = Box::new(move |event: &dyn Any| {
+            if let Some(specific_event) = event.downcast_ref::<E>() {
+                if listener(specific_event).is_break() {
+                    stopped.set(true);
+                }
+            }
+        });
+        self.remember_type::<E>();
+        self.push_listener(TypeId::of::<E>(), boxed_listener, None)
+    }
+}
+
+// Whether a stoppable listener broke since the last `reset`.
+pub(crate) fn stopped(stop_flags: &[Rc<Cell<bool>>]) -> bool {
+    stop_flags.iter().any(|flag| flag.get())
+}
+
+pub(crate) fn reset(stop_flags: &[Rc<Cell<bool>>]) {
+    for flag in stop_flags {
+        flag.set(false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    struct Clicked {
+        x: u32,
+    }
+
+    #[test]
+    fn breaking_stops_the_remaining_listeners() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+
+        let tx_gameplay = tx.clone();
+        let gameplay = event_manager.subscribe(move |event: &Clicked| {
+            let _ = tx_gameplay.send(("gameplay", event.x));
+        });
+        let tx_overlay = tx.clone();
+        event_manager.subscribe_fn(move |event: &Clicked| {
+            let _ = tx_overlay.send(("overlay", event.x));
+        });
+        // The UI covers x < 100 and is ordered first.
+        let tx_ui = tx.clone();
+        let ui = event_manager.subscribe_stoppable(move |event: &Clicked| {
+            if event.x < 100 {
+                let _ = tx_ui.send(("ui", event.x));
+                return ControlFlow::Break(());
+            }
+            ControlFlow::Continue(())
+        });
+        event_manager.order_after::<Clicked>(gameplay, ui).unwrap();
+
+        event_manager.dispatch(&Clicked { x: 40 });
+        event_manager.dispatch(&Clicked { x: 400 });
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![("ui", 40), ("gameplay", 400), ("overlay", 400)]);
+
+        // Frozen managers honour it too.
+        let mut frozen = event_manager.freeze();
+        frozen.dispatch(&Clicked { x: 50 });
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![("ui", 50)]);
+    }
+}
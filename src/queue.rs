@@ -0,0 +1,337 @@
+use std::any::{Any, TypeId};
+use std::collections::{vec_deque, VecDeque};
+use std::fmt;
+use std::iter::Map;
+
+use crate::chain::take_deferred;
+use crate::EventManager;
+
+/// `flush_until_stable` ran out of rounds while events were still being queued.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotConverged {
+    /// How many events were left in the queue.
+    pub pending: usize,
+}
+
+impl fmt::Display for NotConverged {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "event queue did not settle, {} events still pending", self.pending)
+    }
+}
+
+impl std::error::Error for NotConverged {}
+
+/// The events taken out of the queue by `drain_queue`, in the order they were queued.
+pub struct DrainedQueue {
+    events: VecDeque<(TypeId, Box<dyn Any>)>,
+}
+
+impl DrainedQueue {
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+impl IntoIterator for DrainedQueue {
+    type Item = QueuedEvent;
+    type IntoIter = Map<vec_deque::IntoIter<(TypeId, Box<dyn Any>)>, fn((TypeId, Box<dyn Any>)) -> QueuedEvent>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.events.into_iter().map(|(type_id, event)| QueuedEvent { type_id, event })
+    }
+}
+
+/// One event taken out of the queue, still type-erased.
+pub struct QueuedEvent {
+    type_id: TypeId,
+    event: Box<dyn Any>,
+}
+
+impl QueuedEvent {
+    /// The `TypeId` of the event's type.
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    pub fn is<E: Any>(&self) -> bool {
+        self.type_id == TypeId::of::<E>()
+    }
+
+    /// Takes the event out if it is an `E`; otherwise it is dropped and `None` returned.
+    /// Check with `is` first to try several types.
+    pub fn downcast<E: Any>(self) -> Option<E> {
+        self.event.downcast().ok().map(|event| *event)
+    }
+
+    /// The event as a `&dyn Any`, e.g. to hand it to `dispatch_dyn`.
+    pub fn as_any(&self) -> &dyn Any {
+        &*self.event
+    }
+}
+
+impl EventManager {
+    /// Buffers `event` until the next `flush` instead of dispatching it now, e.g. to collect
+    /// a frame's events and handle them at a fixed point of the game loop.
+    pub fn queue<E: Any + 'static>(&mut self, event: E) {
+        self.collect_deferred();
+        self.remember_type::<E>();
+        self.queued.push_back((TypeId::of::<E>(), Box::new(event)));
+    }
+
+    /// Queues `event` like `queue`, unless an equal event is already waiting for `flush`,
+    /// wherever it sits in the queue, e.g. to collapse repeated `RecomputeLayout` requests
+    /// into one. Events are compared with `==` against every queued `E`, so the cost grows
+    /// with the queue. Once flushed or drained, an event no longer blocks equal ones.
+    /// Returns whether `event` was queued.
+    pub fn queue_unique<E: Any + Eq + 'static>(&mut self, event: E) -> bool {
+        self.collect_deferred();
+        let pending = self.queued.iter().any(|(_, queued)| queued.downcast_ref::<E>() == Some(&event));
+        if !pending {
+            self.queue(event);
+        }
+        !pending
+    }
+
+    /// Posts `event` for later processing; the same as `queue`, named for the producer side
+    /// of a producer/consumer split. Producers `post` events as they happen, and the consumer
+    /// handles them all at once with `flush` (or `drain_queue`) when it is ready, whereas
+    /// `dispatch` runs every listener before returning. The queue is unbounded and keeps
+    /// every event: there are no queue limits or coalescing to opt into.
+    pub fn post<E: Any + 'static>(&mut self, event: E) {
+        self.queue(event);
+    }
+
+    /// Dispatches every queued event like `dispatch`, in the order they were queued, and
+    /// returns how many there were. Each event's chained follow-ups run before the next
+    /// queued event.
+    pub fn flush(&mut self) -> usize {
+        self.collect_deferred();
+        let queued = std::mem::take(&mut self.queued);
+        for (type_id, event) in &queued {
+            self.dispatch_erased(*type_id, self.type_name_of(*type_id), &**event);
+        }
+        queued.len()
+    }
+
+    /// Dispatches every queued event like `flush`, but sorted by `key` first, e.g. by a
+    /// timestamp field, for events queued from sources with no common order. `key` sees each
+    /// event as `&dyn Any` and downcasts it as needed. The sort is stable: events with equal
+    /// keys keep the order they were queued in. Returns how many events were dispatched.
+    pub fn flush_sorted<K: Ord>(&mut self, key: impl Fn(&dyn Any) -> K) -> usize {
+        self.collect_deferred();
+        let mut queued = Vec::from(std::mem::take(&mut self.queued));
+        queued.sort_by_cached_key(|(_, event)| key(&**event));
+        for (type_id, event) in &queued {
+            self.dispatch_erased(*type_id, self.type_name_of(*type_id), &**event);
+        }
+        queued.len()
+    }
+
+    /// Takes every queued event out without dispatching it, for callers that schedule them
+    /// themselves: `for event in manager.drain_queue() { ... }` visits them in queue order.
+    pub fn drain_queue(&mut self) -> DrainedQueue {
+        self.collect_deferred();
+        DrainedQueue { events: std::mem::take(&mut self.queued) }
+    }
+
+    /// Flushes the queue again and again until a flush leaves nothing queued, for listeners
+    /// that react to events by `defer`ring more of them, e.g. propagating constraints to a
+    /// fixed point. Returns how many flushes that took, or `NotConverged` once `max_rounds`
+    /// flushes still leave events queued; those stay queued. Only the deferred queue is
+    /// followed: follow-ups `enqueue`d on an `EventEmitter` run within their flush as usual.
+    pub fn flush_until_stable(&mut self, max_rounds: usize) -> Result<usize, NotConverged> {
+        let mut rounds = 0;
+        loop {
+            self.collect_deferred();
+            if self.queued.is_empty() {
+                return Ok(rounds);
+            }
+            if rounds == max_rounds {
+                return Err(NotConverged { pending: self.queued.len() });
+            }
+            self.flush();
+            rounds += 1;
+        }
+    }
+
+    /// Returns how many events are waiting for `flush`, including ones `defer`red by
+    /// chaining listeners.
+    pub fn queued_len(&self) -> usize {
+        let deferred: usize = self.outboxes.iter().map(|outbox| outbox.borrow().deferred_len()).sum();
+        self.queued.len() + deferred
+    }
+
+    // Moves events `defer`red by chaining listeners to the back of the queue.
+    fn collect_deferred(&mut self) {
+        for (type_id, type_name, event) in take_deferred(&self.outboxes) {
+            self.type_names.entry(type_id).or_insert(type_name);
+            self.queued.push_back((type_id, event));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    struct ItemPicked(&'static str);
+    struct DamageTaken(u32);
+
+    #[test]
+    fn flush_dispatches_queued_events_in_order() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<String>();
+
+        let tx_item = tx.clone();
+        event_manager.subscribe(move |event: &ItemPicked| {
+            let _ = tx_item.send(format!("picked {}", event.0));
+        });
+        let tx_damage = tx.clone();
+        event_manager.subscribe(move |event: &DamageTaken| {
+            let _ = tx_damage.send(format!("damage {}", event.0));
+        });
+
+        event_manager.queue(ItemPicked("sword"));
+        event_manager.queue(DamageTaken(3));
+        event_manager.queue(ItemPicked("shield"));
+        assert_eq!(event_manager.queued_len(), 3);
+        assert!(rx.try_iter().next().is_none(), "nothing runs before flush");
+
+        assert_eq!(event_manager.flush(), 3);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["picked sword", "damage 3", "picked shield"]);
+        assert_eq!(event_manager.flush(), 0);
+    }
+
+    #[test]
+    fn drained_events_downcast_in_queue_order() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<u32>();
+        event_manager.subscribe(move |event: &DamageTaken| {
+            let _ = tx.send(event.0);
+        });
+
+        event_manager.queue(DamageTaken(1));
+        event_manager.queue(ItemPicked("sword"));
+        event_manager.queue(DamageTaken(2));
+
+        let drained = event_manager.drain_queue();
+        assert_eq!(drained.len(), 3);
+        assert_eq!(event_manager.queued_len(), 0);
+        let mut damage = Vec::new();
+        for event in drained {
+            if event.is::<ItemPicked>() {
+                assert_eq!(event.downcast::<ItemPicked>().map(|item| item.0), Some("sword"));
+            } else if let Some(DamageTaken(amount)) = event.downcast() {
+                damage.push(amount);
+            }
+        }
+        assert_eq!(damage, vec![1, 2]);
+        assert_eq!(event_manager.flush(), 0);
+        assert!(rx.try_iter().next().is_none(), "drained events are not dispatched");
+    }
+
+    #[test]
+    fn posted_events_wait_for_the_consumer() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<u32>();
+        event_manager.subscribe(move |event: &DamageTaken| {
+            let _ = tx.send(event.0);
+        });
+
+        // The producer side.
+        event_manager.post(DamageTaken(4));
+        event_manager.queue(DamageTaken(5));
+        event_manager.post(DamageTaken(6));
+        assert!(rx.try_iter().next().is_none());
+
+        // The consumer side.
+        assert_eq!(event_manager.flush(), 3);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![4, 5, 6]);
+    }
+
+    #[test]
+    fn flush_sorted_orders_by_key_and_keeps_ties_stable() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<String>();
+        let tx_item = tx.clone();
+        event_manager.subscribe(move |event: &ItemPicked| {
+            let _ = tx_item.send(event.0.to_string());
+        });
+        event_manager.subscribe(move |event: &DamageTaken| {
+            let _ = tx.send(event.0.to_string());
+        });
+
+        event_manager.queue(DamageTaken(3));
+        event_manager.queue(ItemPicked("first"));
+        event_manager.queue(DamageTaken(1));
+        event_manager.queue(ItemPicked("second"));
+        // Items have no timestamp, so they all share key 0 and stay in queue order.
+        let timestamp = |event: &dyn Any| event.downcast_ref::<DamageTaken>().map_or(0, |damage| damage.0);
+        assert_eq!(event_manager.flush_sorted(timestamp), 4);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["first", "second", "1", "3"]);
+    }
+
+    #[test]
+    fn queue_unique_collapses_pending_duplicates() {
+        #[derive(PartialEq, Eq)]
+        struct RecomputeLayout(u32);
+
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<u32>();
+        event_manager.subscribe(move |event: &RecomputeLayout| {
+            let _ = tx.send(event.0);
+        });
+
+        assert!(event_manager.queue_unique(RecomputeLayout(1)));
+        event_manager.queue(ItemPicked("sword"));
+        assert!(event_manager.queue_unique(RecomputeLayout(2)));
+        assert!(!event_manager.queue_unique(RecomputeLayout(1)));
+        assert!(!event_manager.queue_unique(RecomputeLayout(2)));
+        assert_eq!(event_manager.flush(), 3);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![1, 2]);
+
+        assert!(event_manager.queue_unique(RecomputeLayout(1)), "flushed events no longer count");
+    }
+
+    #[test]
+    fn flush_until_stable_follows_deferred_cascades() {
+        struct Edited;
+        struct Relayout;
+        struct Repaint;
+
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<&str>();
+        let tx_edit = tx.clone();
+        event_manager.subscribe_chaining(move |_: &Edited, emitter| {
+            let _ = tx_edit.send("edited");
+            emitter.defer(Relayout);
+        });
+        let tx_layout = tx.clone();
+        event_manager.subscribe_chaining(move |_: &Relayout, emitter| {
+            let _ = tx_layout.send("relayout");
+            emitter.defer(Repaint);
+        });
+        event_manager.subscribe(move |_: &Repaint| {
+            let _ = tx.send("repaint");
+        });
+
+        event_manager.queue(Edited);
+        assert_eq!(event_manager.flush_until_stable(2), Err(NotConverged { pending: 1 }));
+        assert_eq!(event_manager.queued_len(), 1);
+        assert_eq!(event_manager.flush_until_stable(2), Ok(1));
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["edited", "relayout", "repaint"]);
+
+        // Deferring from an immediate dispatch feeds the queue too.
+        event_manager.dispatch(&Edited);
+        assert_eq!(event_manager.queued_len(), 1);
+        assert_eq!(event_manager.flush_until_stable(5), Ok(2));
+        event_manager.queue(Edited);
+        assert_eq!(event_manager.flush_until_stable(5), Ok(3));
+        assert_eq!(event_manager.flush_until_stable(5), Ok(0));
+    }
+}
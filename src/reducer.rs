@@ -0,0 +1,101 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::{EventManager, SubscriptionId};
+
+struct Reducer<S, R, C> {
+    init: S,
+    state: S,
+    dirty: bool,
+    reduce: R,
+    commit: C,
+}
+
+impl EventManager {
+    /// Subscribes a listener to `E` that folds each event into an accumulated state with
+    /// `reduce` instead of reacting right away. `commit_reducers` hands the state to `commit`
+    /// and then resets it to a clone of `init`. A reducer that saw no events since its last
+    /// commit is skipped, so `commit` only runs when there is something to commit.
+    pub fn subscribe_reducer<E: Any + 'static, S: Clone + 'static>(
+        &mut self,
+        init: S,
+        reduce: impl FnMut(&mut S, &E) + 'static,
+        commit: impl FnMut(&S) + 'static,
+    ) -> SubscriptionId {
+        let reducer = Rc::new(RefCell::new(Reducer {
+            state: init.clone(),
+            init,
+            dirty: false,
+            reduce,
+            commit,
+        }));
+
+        // Like throttled listeners, the committer goes away with the listener.
+        let weak = Rc::downgrade(&reducer);
+        self.reducers.push(Box::new(move || {
+            let Some(reducer) = weak.upgrade() else {
+                return false;
+            };
+            let mut reducer = reducer.borrow_mut();
+            if reducer.dirty {
+                let Reducer { init, state, dirty, commit, .. } = &mut *reducer;
+                commit(state);
+                *state = init.clone();
+                *dirty = false;
+            }
+            true
+        }));
+
+        self.subscribe(move |event: &E| {
+            let mut reducer = reducer.borrow_mut();
+            let Reducer { state, dirty, reduce, .. } = &mut *reducer;
+            reduce(state, event);
+            *dirty = true;
+        })
+    }
+
+    /// Runs the `commit` callback of every reducer that accumulated events since the last
+    /// call, in subscription order, and resets their state.
+    pub fn commit_reducers(&mut self) {
+        self.reducers.retain_mut(|commit| commit());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    struct ScoreDelta(i32);
+
+    #[test]
+    fn reducer_commits_accumulated_state_and_resets() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<i32>();
+
+        event_manager.subscribe_reducer(
+            0,
+            |total: &mut i32, event: &ScoreDelta| *total += event.0,
+            move |total: &i32| {
+                let _ = tx.send(*total);
+            },
+        );
+
+        event_manager.dispatch(&ScoreDelta(10));
+        event_manager.dispatch(&ScoreDelta(-3));
+        event_manager.dispatch(&ScoreDelta(5));
+        assert!(rx.try_recv().is_err(), "Nothing is committed before commit_reducers");
+
+        event_manager.commit_reducers();
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![12]);
+
+        // No events since the last commit: nothing to repaint.
+        event_manager.commit_reducers();
+        assert!(rx.try_recv().is_err());
+
+        event_manager.dispatch(&ScoreDelta(1));
+        event_manager.commit_reducers();
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![1], "State was reset after the first commit");
+    }
+}
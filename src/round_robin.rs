@@ -0,0 +1,95 @@
+use std::any::{Any, TypeId};
+
+use crate::{EventManager, Step, SubscriptionId};
+
+impl EventManager {
+    /// Dispatches `event` to exactly one listener of `E`, rotating through them on each call,
+    /// instead of broadcasting it to all of them. This turns the listeners into a pool of
+    /// workers sharing the events between them. Returns the id of the listener that handled
+    /// it, or `None` if `E` has no listeners.
+    ///
+    /// The rotation follows dispatch order and is tracked per type: new listeners join the
+    /// rotation at their dispatch position, and if the last listener served is removed, the
+    /// one that took its place is served next.
+    pub fn dispatch_round_robin<E: Any + 'static>(&mut self, event: &E) -> Option<SubscriptionId> {
+        let type_id = TypeId::of::<E>();
+        let mut ids: Vec<SubscriptionId> = self.listeners.get(&type_id).map(|bucket| bucket.live_ids().collect()).unwrap_or_default();
+        if let Some(shared) = self.shared_listeners.get(&type_id) {
+            ids.extend(shared.iter().map(|entry| entry.id));
+        }
+        if ids.is_empty() {
+            return None;
+        }
+
+        let target = match self.round_robin.get(&type_id) {
+            Some(&(last_id, last_position)) => match ids.iter().position(|id| *id == last_id) {
+                Some(position) => (position + 1) % ids.len(),
+                None => last_position % ids.len(),
+            },
+            None => 0,
+        };
+        self.round_robin.insert(type_id, (ids[target], target));
+
+        self.dispatch_selected(
+            type_id,
+            std::any::type_name::<E>(),
+            event,
+            Some(&mut |position, _| match position {
+                position if position < target => Step::Skip,
+                position if position == target => Step::Run,
+                _ => Step::Stop,
+            }),
+        );
+        self.flush_chained();
+        Some(ids[target])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    struct Job(u32);
+
+    fn worker(event_manager: &mut EventManager, tx: &mpsc::Sender<(&'static str, u32)>, name: &'static str) -> SubscriptionId {
+        let tx = tx.clone();
+        event_manager.subscribe(move |job: &Job| {
+            let _ = tx.send((name, job.0));
+        })
+    }
+
+    #[test]
+    fn round_robin_rotates_and_survives_removal() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+        assert_eq!(event_manager.dispatch_round_robin(&Job(0)), None);
+
+        worker(&mut event_manager, &tx, "a");
+        let tx_b = tx.clone();
+        event_manager.subscribe_fn(move |job: &Job| {
+            let _ = tx_b.send(("b", job.0));
+        });
+
+        for job in 1..=3 {
+            event_manager.dispatch_round_robin(&Job(job));
+        }
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![("a", 1), ("b", 2), ("a", 3)]);
+
+        // A worker that removes itself once served; `c` then shifts into its slot and is next.
+        // Mutable listeners come first in dispatch order, so both run before `b`.
+        let tx_once = tx.clone();
+        event_manager.wait_for_all(&[TypeId::of::<Job>()], move || {
+            let _ = tx_once.send(("once", 0));
+        });
+        let c = worker(&mut event_manager, &tx, "c");
+        for job in 4..=7 {
+            event_manager.dispatch_round_robin(&Job(job));
+        }
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            vec![("once", 0), ("c", 5), ("b", 6), ("a", 7)]
+        );
+        assert_eq!(event_manager.dispatch_round_robin(&Job(8)), Some(c));
+    }
+}
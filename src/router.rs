@@ -0,0 +1,152 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::EventManager;
+
+// Pulls the routing key out of a type-erased event of the type it was registered for.
+type KeyExtractor<K> = Box<dyn Fn(&dyn Any) -> K>;
+// Builds the child for a key that has none yet.
+type ChildFactory<K> = Box<dyn FnMut(&K) -> EventManager>;
+
+/// Forwards events to one of several child `EventManager`s, picked by a key taken from
+/// the event itself, e.g. a per-player manager keyed by player id.
+///
+/// Each routed event type registers how to extract its key with `route`. When no child
+/// exists for a key the event is dropped, unless `create_missing_with` has installed a
+/// factory, in which case the child is created on demand and receives the event.
+pub struct EventRouter<K> {
+    children: HashMap<K, EventManager>,
+    extractors: HashMap<TypeId, KeyExtractor<K>>,
+    create_missing: Option<ChildFactory<K>>,
+}
+
+impl<K: Hash + Eq + Clone> Default for EventRouter<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone> EventRouter<K> {
+    /// Creates a router with no children that drops events for unknown keys.
+    pub fn new() -> Self {
+        EventRouter {
+            children: HashMap::new(),
+            extractors: HashMap::new(),
+            create_missing: None,
+        }
+    }
+
+    /// Routes events of type `E` by the key `extract` returns for them, replacing any
+    /// previous extractor for `E`.
+    pub fn route<E: Any + 'static>(&mut self, extract: impl Fn(&E) -> K + 'static) {
+        let extractor: KeyExtractor<K> = Box::new(move |event| {
+            // Only ever called with events of the type it was registered under.
+            extract(event.downcast_ref::<E>().expect("router extractor called with the wrong event type"))
+        });
+        self.extractors.insert(TypeId::of::<E>(), extractor);
+    }
+
+    /// Creates a missing child with `factory` when an event is routed to its key,
+    /// instead of dropping the event.
+    pub fn create_missing_with(&mut self, factory: impl FnMut(&K) -> EventManager + 'static) {
+        self.create_missing = Some(Box::new(factory));
+    }
+
+    /// Adds `child` under `key`, returning the child it replaced, if any.
+    pub fn insert_child(&mut self, key: K, child: EventManager) -> Option<EventManager> {
+        self.children.insert(key, child)
+    }
+
+    /// Removes and returns the child under `key`.
+    pub fn remove_child(&mut self, key: &K) -> Option<EventManager> {
+        self.children.remove(key)
+    }
+
+    pub fn child(&self, key: &K) -> Option<&EventManager> {
+        self.children.get(key)
+    }
+
+    pub fn child_mut(&mut self, key: &K) -> Option<&mut EventManager> {
+        self.children.get_mut(key)
+    }
+
+    /// Returns how many children the router holds.
+    pub fn child_count(&self) -> usize {
+        self.children.len()
+    }
+
+    /// Dispatches `event` to the child its key selects. Returns false if the event was
+    /// dropped, either because `E` has no route or because no child exists for its key
+    /// and none may be created.
+    pub fn dispatch<E: Any + 'static>(&mut self, event: &E) -> bool {
+        let Some(extract) = self.extractors.get(&TypeId::of::<E>()) else {
+            return false;
+        };
+        let key = extract(event);
+        if !self.children.contains_key(&key) {
+            let Some(factory) = self.create_missing.as_mut() else {
+                return false;
+            };
+            let child = factory(&key);
+            self.children.insert(key.clone(), child);
+        }
+        if let Some(child) = self.children.get_mut(&key) {
+            child.dispatch(event);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    struct PlayerEvent {
+        player_id: u32,
+        score: u32,
+    }
+
+    fn recording_child(tx: &mpsc::Sender<(u32, u32)>, player_id: u32) -> EventManager {
+        let tx = tx.clone();
+        let mut child = EventManager::new();
+        child.subscribe(move |event: &PlayerEvent| {
+            let _ = tx.send((player_id, event.score));
+        });
+        child
+    }
+
+    #[test]
+    fn events_reach_the_child_for_their_key() {
+        let mut router = EventRouter::new();
+        let (tx, rx) = mpsc::channel();
+        router.route(|event: &PlayerEvent| event.player_id);
+        router.insert_child(1, recording_child(&tx, 1));
+        router.insert_child(2, recording_child(&tx, 2));
+
+        assert!(router.dispatch(&PlayerEvent { player_id: 2, score: 10 }));
+        assert!(router.dispatch(&PlayerEvent { player_id: 1, score: 20 }));
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![(2, 10), (1, 20)]);
+
+        // Unknown keys and unrouted types are dropped.
+        assert!(!router.dispatch(&PlayerEvent { player_id: 3, score: 30 }));
+        assert!(!router.dispatch(&5u32));
+        assert!(rx.try_iter().next().is_none());
+        assert_eq!(router.child_count(), 2);
+    }
+
+    #[test]
+    fn missing_children_can_be_created_on_demand() {
+        let mut router = EventRouter::new();
+        let (tx, rx) = mpsc::channel();
+        router.route(|event: &PlayerEvent| event.player_id);
+        router.create_missing_with(move |&player_id| recording_child(&tx, player_id));
+
+        assert!(router.dispatch(&PlayerEvent { player_id: 7, score: 1 }));
+        assert!(router.dispatch(&PlayerEvent { player_id: 7, score: 2 }));
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![(7, 1), (7, 2)]);
+        assert_eq!(router.child_count(), 1);
+        assert!(router.child(&7).is_some());
+    }
+}
@@ -0,0 +1,107 @@
+use std::any::{Any, TypeId};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+use crate::{EventManager, Step};
+
+// A SplitMix64 generator: tiny, fast and good enough for load shedding.
+pub(crate) struct SampleRng {
+    state: u64,
+}
+
+impl SampleRng {
+    pub(crate) fn from_seed(seed: u64) -> Self {
+        SampleRng { state: seed }
+    }
+
+    // Seeds from the per-process random keys std already uses for `HashMap`.
+    pub(crate) fn from_entropy() -> Self {
+        Self::from_seed(RandomState::new().build_hasher().finish())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // A uniform value in `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+impl EventManager {
+    /// Dispatches `event`, invoking each listener independently with probability `rate`
+    /// (clamped to `0.0..=1.0`). This is deliberately lossy: use it to shed load on
+    /// high-frequency events where statistical coverage is enough, never for events whose
+    /// handling matters for correctness. Returns how many listeners were invoked.
+    pub fn dispatch_sampled<E: Any + 'static>(&mut self, event: &E, rate: f64) -> usize {
+        let rate = rate.clamp(0.0, 1.0);
+        // The generator lives on `self`, so take it out while the listeners are borrowed.
+        let mut rng = std::mem::replace(&mut self.sample_rng, SampleRng::from_seed(0));
+        let invoked = self.dispatch_selected(TypeId::of::<E>(), std::any::type_name::<E>(), event, Some(&mut |_, _| {
+            if rng.next_f64() < rate {
+                Step::Run
+            } else {
+                Step::Skip
+            }
+        }));
+        self.sample_rng = rng;
+        self.flush_chained();
+        invoked
+    }
+
+    /// Reseeds the generator behind `dispatch_sampled`, making the sampling reproducible.
+    /// Managers start from a random seed.
+    pub fn set_sample_seed(&mut self, seed: u64) {
+        self.sample_rng = SampleRng::from_seed(seed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    struct FrameMetric;
+
+    fn manager_with_listeners(count: usize, seed: u64) -> (EventManager, mpsc::Receiver<usize>) {
+        let mut event_manager = EventManager::new();
+        event_manager.set_sample_seed(seed);
+        let (tx, rx) = mpsc::channel();
+        for index in 0..count {
+            let tx = tx.clone();
+            event_manager.subscribe(move |_: &FrameMetric| {
+                let _ = tx.send(index);
+            });
+        }
+        (event_manager, rx)
+    }
+
+    #[test]
+    fn dispatch_sampled_is_reproducible_with_a_seed() {
+        let (mut first, first_rx) = manager_with_listeners(100, 42);
+        let (mut second, second_rx) = manager_with_listeners(100, 42);
+
+        let first_count = first.dispatch_sampled(&FrameMetric, 0.5);
+        let second_count = second.dispatch_sampled(&FrameMetric, 0.5);
+
+        let first_hits: Vec<usize> = first_rx.try_iter().collect();
+        assert_eq!(first_hits, second_rx.try_iter().collect::<Vec<_>>());
+        assert_eq!(first_count, second_count);
+        assert!((25..=75).contains(&first_count), "sampled {first_count} of 100");
+    }
+
+    #[test]
+    fn dispatch_sampled_respects_rate_bounds() {
+        let (mut event_manager, rx) = manager_with_listeners(10, 7);
+
+        assert_eq!(event_manager.dispatch_sampled(&FrameMetric, 0.0), 0);
+        assert_eq!(event_manager.dispatch_sampled(&FrameMetric, 1.0), 10);
+        assert_eq!(event_manager.dispatch_sampled(&FrameMetric, 3.0), 10);
+        assert_eq!(rx.try_iter().count(), 20);
+    }
+}
@@ -0,0 +1,120 @@
+use std::any::TypeId;
+use std::time::{Duration, Instant};
+
+use crate::{EventManager, Step, SubscriptionId};
+
+/// A listener invocation that took longer than the threshold given to
+/// `set_slow_listener_threshold`.
+#[derive(Debug, Clone)]
+pub struct SlowListener {
+    /// The type name of the event being dispatched.
+    pub event: &'static str,
+    pub id: SubscriptionId,
+    /// The name given to `subscribe_named`, if any.
+    pub name: Option<&'static str>,
+    pub elapsed: Duration,
+}
+
+// The threshold and hook set by `set_slow_listener_threshold`.
+pub(crate) struct SlowListenerMonitor {
+    pub(crate) threshold: Duration,
+    hook: Box<dyn FnMut(&SlowListener)>,
+}
+
+// Times the listeners of one dispatch through the `select` callback: each listener runs
+// until `select` is asked about the next one, or until `finish`.
+pub(crate) struct ListenerTimer {
+    threshold: Duration,
+    running: Option<(SubscriptionId, Instant)>,
+    slow: Vec<(SubscriptionId, Duration)>,
+}
+
+impl ListenerTimer {
+    pub(crate) fn new(threshold: Duration) -> Self {
+        ListenerTimer { threshold, running: None, slow: Vec::new() }
+    }
+
+    // Stops timing the previous listener and, if `step` runs this one, starts timing it.
+    pub(crate) fn time(&mut self, id: SubscriptionId, step: Step) -> Step {
+        self.stop();
+        if step == Step::Run {
+            self.running = Some((id, Instant::now()));
+        }
+        step
+    }
+
+    pub(crate) fn finish(mut self) -> Vec<(SubscriptionId, Duration)> {
+        self.stop();
+        self.slow
+    }
+
+    fn stop(&mut self) {
+        if let Some((id, started)) = self.running.take() {
+            let elapsed = started.elapsed();
+            if elapsed > self.threshold {
+                self.slow.push((id, elapsed));
+            }
+        }
+    }
+}
+
+impl EventManager {
+    /// Times every listener invocation from now on and calls `hook` after each dispatch for
+    /// every listener that took longer than `threshold`, e.g. to log handlers that stall a
+    /// frame. Purely observational: slow listeners still run to completion. Applies to the
+    /// same dispatches as dispatch statistics; frozen managers, `dispatch_shared` and the
+    /// specialised listener kinds aren't timed. Replaces any previous threshold and hook.
+    pub fn set_slow_listener_threshold(&mut self, threshold: Duration, hook: impl FnMut(&SlowListener) + 'static) {
+        self.slow_listeners = Some(SlowListenerMonitor { threshold, hook: Box::new(hook) });
+    }
+
+    /// Stops timing listeners.
+    pub fn clear_slow_listener_threshold(&mut self) {
+        self.slow_listeners = None;
+    }
+
+    pub(crate) fn report_slow(&mut self, type_id: TypeId, type_name: &'static str, slow: Vec<(SubscriptionId, Duration)>) {
+        let Some(monitor) = self.slow_listeners.as_mut() else {
+            return;
+        };
+        let names = self.names.get(&type_id);
+        for (id, elapsed) in slow {
+            let name = names.and_then(|names| names.get(&id)).copied();
+            (monitor.hook)(&SlowListener { event: type_name, id, name, elapsed });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::thread;
+
+    struct PhysicsStep;
+
+    #[test]
+    fn listeners_over_the_threshold_are_reported() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+
+        // The empty listeners stay far below the threshold even if the test thread stalls;
+        // the slow one always exceeds it, as `thread::sleep` never returns early.
+        event_manager.subscribe(|_: &PhysicsStep| {});
+        let slow = event_manager.subscribe_named("broadphase", |_: &PhysicsStep| thread::sleep(Duration::from_millis(250)));
+        event_manager.subscribe_fn(|_: &PhysicsStep| {});
+        event_manager.set_slow_listener_threshold(Duration::from_millis(200), move |report| {
+            let _ = tx.send((report.id, report.name, report.elapsed));
+        });
+
+        event_manager.dispatch(&PhysicsStep);
+        let reports: Vec<_> = rx.try_iter().collect();
+        assert_eq!(reports.len(), 1);
+        assert_eq!((reports[0].0, reports[0].1), (slow, Some("broadphase")));
+        assert!(reports[0].2 >= Duration::from_millis(250));
+
+        event_manager.clear_slow_listener_threshold();
+        event_manager.dispatch(&PhysicsStep);
+        assert!(rx.try_iter().next().is_none());
+    }
+}
@@ -0,0 +1,182 @@
+use std::any::TypeId;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::EventManager;
+
+/// Dispatch statistics accumulated between two calls to `EventManager::take_stats`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrameStats {
+    /// Number of events dispatched, counting each type an aliased dispatch reached.
+    pub events_dispatched: u64,
+    /// Number of listener calls made across all those dispatches.
+    pub listener_invocations: u64,
+    /// The event types that spent the most time in dispatch, busiest first.
+    pub busiest: Vec<TypeStats>,
+}
+
+/// Per-event-type figures reported in `FrameStats::busiest`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeStats {
+    /// The type name of the event, as reported by `std::any::type_name`, or its `Event::NAME`
+    /// when the first dispatch of the type since the last `take_stats` was a `dispatch_event`.
+    pub name: &'static str,
+    /// Number of times this type was dispatched.
+    pub dispatches: u64,
+    /// Total time spent running this type's listeners.
+    pub time: Duration,
+}
+
+// The live counters behind `EventManager::take_stats`.
+pub(crate) struct StatsCollector {
+    top_n: usize,
+    events_dispatched: u64,
+    listener_invocations: u64,
+    per_type: HashMap<TypeId, TypeStats>,
+    // Dispatches per type name since stats were enabled; `take_stats` leaves these alone.
+    totals: HashMap<&'static str, u64>,
+}
+
+impl StatsCollector {
+    pub(crate) fn record(&mut self, type_id: TypeId, name: &'static str, invoked: usize, elapsed: Duration) {
+        self.events_dispatched += 1;
+        self.listener_invocations += invoked as u64;
+        let entry = self.per_type.entry(type_id).or_insert(TypeStats {
+            name,
+            dispatches: 0,
+            time: Duration::ZERO,
+        });
+        entry.dispatches += 1;
+        entry.time += elapsed;
+        *self.totals.entry(name).or_default() += 1;
+    }
+}
+
+impl EventManager {
+    /// Starts collecting dispatch statistics, reporting the `top_n` busiest event types
+    /// from each `take_stats`. Collection costs two clock reads and a map update per dispatch.
+    /// Statistics are per type reached, so a dispatch that follows aliases counts once for
+    /// its own type and once for every aliased type, each with the listeners it ran there.
+    /// Calling it again resets the counters.
+    pub fn enable_stats(&mut self, top_n: usize) {
+        self.stats = Some(StatsCollector {
+            top_n,
+            events_dispatched: 0,
+            listener_invocations: 0,
+            per_type: HashMap::new(),
+            totals: HashMap::new(),
+        });
+    }
+
+    /// Returns how many times each event type has been dispatched since `enable_stats`,
+    /// keyed by type name, or by `Event::NAME` for `dispatch_event`, e.g. to serve from a
+    /// metrics endpoint. Unlike `take_stats` it doesn't reset anything. Returns an empty map
+    /// when collection is off.
+    pub fn counts_by_name(&self) -> HashMap<&'static str, u64> {
+        self.stats.as_ref().map(|stats| stats.totals.clone()).unwrap_or_default()
+    }
+
+    /// Stops collecting dispatch statistics and discards anything not yet taken.
+    pub fn disable_stats(&mut self) {
+        self.stats = None;
+    }
+
+    /// Returns the statistics gathered since the last call and resets the counters,
+    /// so it can be called once per frame. Returns empty stats when collection is off.
+    pub fn take_stats(&mut self) -> FrameStats {
+        let Some(stats) = self.stats.as_mut() else {
+            return FrameStats::default();
+        };
+
+        let mut busiest: Vec<TypeStats> = stats.per_type.drain().map(|(_, type_stats)| type_stats).collect();
+        busiest.sort_by_key(|type_stats| Reverse(type_stats.time));
+        busiest.truncate(stats.top_n);
+
+        let frame = FrameStats {
+            events_dispatched: stats.events_dispatched,
+            listener_invocations: stats.listener_invocations,
+            busiest,
+        };
+        stats.events_dispatched = 0;
+        stats.listener_invocations = 0;
+        frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    struct Tick;
+    struct SlowEvent;
+
+    #[test]
+    fn take_stats_reports_and_resets_counts() {
+        let mut event_manager = EventManager::new();
+        event_manager.enable_stats(1);
+
+        event_manager.subscribe(|_: &Tick| {});
+        event_manager.subscribe(|_: &Tick| {});
+        event_manager.subscribe(|_: &SlowEvent| thread::sleep(Duration::from_millis(5)));
+
+        event_manager.dispatch(&Tick);
+        event_manager.dispatch(&Tick);
+        event_manager.dispatch(&SlowEvent);
+
+        let stats = event_manager.take_stats();
+        assert_eq!(stats.events_dispatched, 3);
+        assert_eq!(stats.listener_invocations, 5);
+        assert_eq!(stats.busiest.len(), 1);
+        assert_eq!(stats.busiest[0].name, std::any::type_name::<SlowEvent>());
+        assert_eq!(stats.busiest[0].dispatches, 1);
+
+        let stats = event_manager.take_stats();
+        assert_eq!(stats, FrameStats::default(), "Counters should reset after take_stats");
+    }
+
+    #[test]
+    fn take_stats_is_empty_when_disabled() {
+        let mut event_manager = EventManager::new();
+        event_manager.subscribe(|_: &Tick| {});
+        event_manager.dispatch(&Tick);
+
+        assert_eq!(event_manager.take_stats(), FrameStats::default());
+        assert!(event_manager.counts_by_name().is_empty());
+    }
+
+    #[test]
+    fn counts_by_name_accumulate_across_take_stats() {
+        let mut event_manager = EventManager::new();
+        event_manager.enable_stats(0);
+        event_manager.subscribe(|_: &Tick| {});
+
+        event_manager.dispatch(&Tick);
+        event_manager.dispatch(&Tick);
+        event_manager.take_stats();
+        event_manager.dispatch(&Tick);
+        event_manager.dispatch(&SlowEvent);
+
+        let counts = event_manager.counts_by_name();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[std::any::type_name::<Tick>()], 3);
+        assert_eq!(counts[std::any::type_name::<SlowEvent>()], 1);
+    }
+
+    #[test]
+    fn aliased_dispatches_count_for_every_type_reached() {
+        let mut event_manager = EventManager::new();
+        event_manager.enable_stats(2);
+        event_manager.subscribe(|_: &Tick| {});
+        event_manager.subscribe(|_: &SlowEvent| {});
+        event_manager.subscribe(|_: &SlowEvent| {});
+        event_manager.alias_event(|_: &Tick| SlowEvent);
+
+        event_manager.dispatch(&Tick);
+        let stats = event_manager.take_stats();
+        assert_eq!((stats.events_dispatched, stats.listener_invocations), (2, 3));
+        let counts = event_manager.counts_by_name();
+        assert_eq!((counts[std::any::type_name::<Tick>()], counts[std::any::type_name::<SlowEvent>()]), (1, 1));
+    }
+}
@@ -0,0 +1,300 @@
+use std::any::{Any, TypeId};
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::BuildHasher;
+use std::rc::Rc;
+
+use crate::{Listener, ListenerEntry, Step, SubscriptionId};
+
+/// The listeners an `EventManager` holds for one event type.
+/// Opaque: it only exists so custom `ListenerStore`s can hold it.
+///
+/// The closures are kept in their own vector, index-aligned with their metadata, so a plain
+/// dispatch walks nothing but closures.
+#[derive(Default)]
+pub struct ListenerBucket {
+    listeners: Vec<Listener>,
+    meta: Vec<ListenerMeta>,
+    // How many entries can retire themselves; while zero, plain dispatch never reads `meta`.
+    self_removing: usize,
+    // The sequence number the next pushed entry gets.
+    next_seq: u64,
+}
+
+struct ListenerMeta {
+    id: SubscriptionId,
+    retired: Option<Rc<Cell<bool>>>,
+    // Increases with every push, so it records subscription order even where ids don't,
+    // e.g. for ids chosen with `subscribe_with_id`.
+    seq: u64,
+}
+
+impl ListenerMeta {
+    fn is_retired(&self) -> bool {
+        self.retired.as_ref().is_some_and(|retired| retired.get())
+    }
+}
+
+impl ListenerBucket {
+    pub(crate) fn push(&mut self, entry: ListenerEntry) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.restore([(seq, entry)]);
+    }
+
+    pub(crate) fn extend(&mut self, entries: impl IntoIterator<Item = ListenerEntry>) {
+        for entry in entries {
+            self.push(entry);
+        }
+    }
+
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        self.listeners.reserve(additional);
+        self.meta.reserve(additional);
+    }
+
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.listeners.shrink_to_fit();
+        self.meta.shrink_to_fit();
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.listeners.is_empty()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.listeners.len()
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.listeners.capacity()
+    }
+
+    // The combined size of the boxed closures themselves, not counting what they point to.
+    pub(crate) fn closure_bytes(&self) -> usize {
+        self.listeners.iter().map(|listener| std::mem::size_of_val(&**listener)).sum()
+    }
+
+    // Listeners that haven't retired themselves.
+    pub(crate) fn live_count(&self) -> usize {
+        self.meta.iter().filter(|meta| !meta.is_retired()).count()
+    }
+
+    // Ids of the listeners that haven't retired themselves, in dispatch order.
+    pub(crate) fn live_ids(&self) -> impl Iterator<Item = SubscriptionId> + '_ {
+        self.meta.iter().filter(|meta| !meta.is_retired()).map(|meta| meta.id)
+    }
+
+    // Ids of every entry, retired ones included, in dispatch order.
+    pub(crate) fn ids(&self) -> impl Iterator<Item = SubscriptionId> + '_ {
+        self.meta.iter().map(|meta| meta.id)
+    }
+
+    pub(crate) fn contains_live(&self, id: SubscriptionId) -> bool {
+        self.meta.iter().any(|meta| meta.id == id && !meta.is_retired())
+    }
+
+    // Whether the listener `id` can retire itself, e.g. a `subscribe_once` listener.
+    pub(crate) fn is_self_removing(&self, id: SubscriptionId) -> bool {
+        self.meta.iter().any(|meta| meta.id == id && meta.retired.is_some())
+    }
+
+    // Removes the live listener `id`, keeping the order of the rest. Returns whether it was here.
+    pub(crate) fn remove(&mut self, id: SubscriptionId) -> bool {
+        let Some(index) = self.meta.iter().position(|meta| meta.id == id && !meta.is_retired()) else {
+            return false;
+        };
+        drop(self.listeners.remove(index));
+        let meta = self.meta.remove(index);
+        self.self_removing -= usize::from(meta.retired.is_some());
+        true
+    }
+
+    // Takes every entry out, in dispatch order, retired ones included.
+    pub(crate) fn take_entries(&mut self) -> Vec<ListenerEntry> {
+        self.take_sequenced().into_iter().map(|(_, entry)| entry).collect()
+    }
+
+    // Like `take_entries`, pairing each entry with its sequence number for `restore`.
+    pub(crate) fn take_sequenced(&mut self) -> Vec<(u64, ListenerEntry)> {
+        self.self_removing = 0;
+        self.listeners
+            .drain(..)
+            .zip(self.meta.drain(..))
+            .map(|(listener, meta)| (meta.seq, ListenerEntry { id: meta.id, listener, retired: meta.retired }))
+            .collect()
+    }
+
+    // Appends entries taken with `take_sequenced`, keeping their sequence numbers.
+    pub(crate) fn restore(&mut self, entries: impl IntoIterator<Item = (u64, ListenerEntry)>) {
+        for (seq, entry) in entries {
+            self.self_removing += usize::from(entry.retired.is_some());
+            self.listeners.push(entry.listener);
+            self.meta.push(ListenerMeta { id: entry.id, retired: entry.retired, seq });
+        }
+    }
+
+    pub(crate) fn into_entries(mut self) -> Vec<ListenerEntry> {
+        self.take_entries()
+    }
+
+    // Calls the live listeners in order, asking `select` about each one when given (see
+    // `EventManager::dispatch_selected`); `position` is advanced past every live listener.
+    // Returns how many listeners were invoked.
+    pub(crate) fn run<'s>(
+        &mut self,
+        event: &dyn Any,
+        position: &mut usize,
+        mut select: Option<&mut (dyn FnMut(usize, SubscriptionId) -> Step + 's)>,
+    ) -> usize {
+        if select.is_none() && self.self_removing == 0 {
+            for listener in &mut self.listeners {
+                listener(event);
+            }
+            *position += self.listeners.len();
+            return self.listeners.len();
+        }
+
+        let mut invoked = 0;
+        for (listener, meta) in self.listeners.iter_mut().zip(&self.meta) {
+            if meta.is_retired() {
+                continue;
+            }
+            match select.as_mut().map_or(Step::Run, |select| select(*position, meta.id)) {
+                Step::Run => {
+                    listener(event);
+                    invoked += 1;
+                }
+                Step::Skip => {}
+                Step::Stop => break,
+            }
+            *position += 1;
+        }
+        invoked
+    }
+
+    // Drops listeners that retired themselves. Returns the ids of those removed.
+    pub(crate) fn prune_retired(&mut self) -> Vec<SubscriptionId> {
+        if self.self_removing == 0 || !self.meta.iter().any(ListenerMeta::is_retired) {
+            return Vec::new();
+        }
+        let pruned = self.meta.iter().filter(|meta| meta.is_retired()).map(|meta| meta.id).collect();
+        let keep: Vec<bool> = self.meta.iter().map(|meta| !meta.is_retired()).collect();
+        let mut index = 0;
+        self.listeners.retain(|_| {
+            index += 1;
+            keep[index - 1]
+        });
+        self.meta.retain(|meta| !meta.is_retired());
+        self.self_removing = self.meta.iter().filter(|meta| meta.retired.is_some()).count();
+        pruned
+    }
+}
+
+/// The map from event `TypeId` to listeners behind an `EventManager`.
+///
+/// Implemented for `HashMap<TypeId, ListenerBucket, S>` with any hasher (the default manager
+/// uses `RandomState`) and for `BTreeMap<TypeId, ListenerBucket>`, which iterates types in a
+/// deterministic order. `TypeId`s are already hashes, so a cheap hasher such as Fx is a drop-in
+/// way to cut lookup cost: `EventManager::with_store(HashMap::with_hasher(FxBuildHasher))`.
+pub trait ListenerStore {
+    fn get(&self, type_id: &TypeId) -> Option<&ListenerBucket>;
+    fn get_mut(&mut self, type_id: &TypeId) -> Option<&mut ListenerBucket>;
+    /// Returns the bucket for `type_id`, inserting an empty one if needed.
+    fn get_or_default(&mut self, type_id: TypeId) -> &mut ListenerBucket;
+    fn remove(&mut self, type_id: &TypeId) -> Option<ListenerBucket>;
+    fn iter(&self) -> Box<dyn Iterator<Item = (&TypeId, &ListenerBucket)> + '_>;
+    /// Removes and returns every bucket.
+    fn drain(&mut self) -> Vec<(TypeId, ListenerBucket)>;
+    /// How many types the store can hold without reallocating, if it can tell.
+    fn capacity(&self) -> Option<usize> {
+        None
+    }
+    /// Releases spare capacity, if the store keeps any.
+    fn shrink_to_fit(&mut self) {}
+}
+
+impl<S: BuildHasher> ListenerStore for HashMap<TypeId, ListenerBucket, S> {
+    fn get(&self, type_id: &TypeId) -> Option<&ListenerBucket> {
+        HashMap::get(self, type_id)
+    }
+
+    fn get_mut(&mut self, type_id: &TypeId) -> Option<&mut ListenerBucket> {
+        HashMap::get_mut(self, type_id)
+    }
+
+    fn get_or_default(&mut self, type_id: TypeId) -> &mut ListenerBucket {
+        self.entry(type_id).or_default()
+    }
+
+    fn remove(&mut self, type_id: &TypeId) -> Option<ListenerBucket> {
+        HashMap::remove(self, type_id)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (&TypeId, &ListenerBucket)> + '_> {
+        Box::new(HashMap::iter(self))
+    }
+
+    fn drain(&mut self) -> Vec<(TypeId, ListenerBucket)> {
+        HashMap::drain(self).collect()
+    }
+
+    fn capacity(&self) -> Option<usize> {
+        Some(HashMap::capacity(self))
+    }
+
+    fn shrink_to_fit(&mut self) {
+        HashMap::shrink_to_fit(self);
+    }
+}
+
+impl ListenerStore for BTreeMap<TypeId, ListenerBucket> {
+    fn get(&self, type_id: &TypeId) -> Option<&ListenerBucket> {
+        BTreeMap::get(self, type_id)
+    }
+
+    fn get_mut(&mut self, type_id: &TypeId) -> Option<&mut ListenerBucket> {
+        BTreeMap::get_mut(self, type_id)
+    }
+
+    fn get_or_default(&mut self, type_id: TypeId) -> &mut ListenerBucket {
+        self.entry(type_id).or_default()
+    }
+
+    fn remove(&mut self, type_id: &TypeId) -> Option<ListenerBucket> {
+        BTreeMap::remove(self, type_id)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (&TypeId, &ListenerBucket)> + '_> {
+        Box::new(BTreeMap::iter(self))
+    }
+
+    fn drain(&mut self) -> Vec<(TypeId, ListenerBucket)> {
+        std::mem::take(self).into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EventManager;
+    use std::sync::mpsc;
+
+    struct Ping(u32);
+
+    #[test]
+    fn manager_works_with_a_btree_store() {
+        let mut event_manager = EventManager::with_store(BTreeMap::new());
+        let (tx, rx) = mpsc::channel::<u32>();
+
+        event_manager.subscribe(move |event: &Ping| {
+            let _ = tx.send(event.0);
+        });
+        event_manager.dispatch(&Ping(7));
+        assert_eq!(rx.try_recv(), Ok(7));
+
+        assert_eq!(event_manager.drain_type::<Ping>().len(), 1);
+        event_manager.dispatch(&Ping(8));
+        assert!(rx.try_recv().is_err());
+    }
+}
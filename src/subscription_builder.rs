@@ -0,0 +1,195 @@
+use std::any::{Any, TypeId};
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use crate::{EventManager, Phase, SubscriptionId};
+
+type Filter<E> = Box<dyn Fn(&E) -> bool>;
+
+/// Combines subscription options that otherwise each have their own `subscribe_*` method,
+/// e.g. `manager.subscription::<E>().once().with_priority(5).filter(..).subscribe(..)`.
+/// Every option is independent of the others, and each behaves exactly like its dedicated
+/// method would on its own.
+#[must_use = "nothing is subscribed until `subscribe` is called"]
+pub struct SubscriptionBuilder<'m, E> {
+    event_manager: &'m mut EventManager,
+    once: bool,
+    priority: Option<i32>,
+    phase: Option<Phase>,
+    tag: Option<String>,
+    filter: Option<Filter<E>>,
+    event: PhantomData<fn(&E)>,
+}
+
+impl<E: Any + 'static> SubscriptionBuilder<'_, E> {
+    /// Removes the listener after the first event it handles, like `subscribe_once`, along
+    /// with its priority, phase and tag. Events rejected by `filter` don't count.
+    pub fn once(mut self) -> Self {
+        self.once = true;
+        self
+    }
+
+    /// Runs the listener at `priority`, like `subscribe_with_priority`.
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Runs the listener in `phase`, like `subscribe_in_phase`.
+    pub fn in_phase(mut self, phase: Phase) -> Self {
+        self.phase = Some(phase);
+        self
+    }
+
+    /// Puts the listener in the tag group `tag`, like `subscribe_tagged`.
+    pub fn tagged(mut self, tag: &str) -> Self {
+        self.tag = Some(tag.to_string());
+        self
+    }
+
+    /// Only hands the listener events for which `filter` returns true. Replaces any earlier
+    /// filter.
+    pub fn filter(mut self, filter: impl Fn(&E) -> bool + 'static) -> Self {
+        self.filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Subscribes `listener` with the chosen options.
+    pub fn subscribe(self, mut listener: impl FnMut(&E) + 'static) -> SubscriptionId {
+        let SubscriptionBuilder { event_manager, once, priority, phase, tag, filter, .. } = self;
+        let type_id = TypeId::of::<E>();
+        let retired = once.then(|| Rc::new(Cell::new(false)));
+        let retired_flag = retired.clone();
+        let boxed_listener = Box::new(move |event: &dyn Any| {
+            let Some(specific_event) = event.downcast_ref::<E>() else {
+                return;
+            };
+            if filter.as_ref().is_some_and(|filter| !filter(specific_event)) {
+                return;
+            }
+            if let Some(retired) = &retired_flag {
+                if retired.get() {
+                    return;
+                }
+                retired.set(true);
+            }
+            listener(specific_event);
+        });
+
+        event_manager.remember_type::<E>();
+        let id = event_manager.push_listener(type_id, boxed_listener, retired);
+        if let Some(priority) = priority {
+            event_manager.priorities.entry(type_id).or_default().insert(id, priority);
+        }
+        if let Some(phase) = phase {
+            event_manager.phases.entry(type_id).or_default().insert(id, phase);
+        }
+        if let Some(tag) = tag {
+            event_manager.tags.entry(type_id).or_default().insert(id, tag);
+        }
+        // The new listener has no ordering constraints, so this can't introduce a cycle.
+        let _ = event_manager.reorder(type_id);
+        id
+    }
+}
+
+impl EventManager {
+    /// Starts a subscription to `E` that combines several options, see `SubscriptionBuilder`.
+    pub fn subscription<E: Any + 'static>(&mut self) -> SubscriptionBuilder<'_, E> {
+        SubscriptionBuilder {
+            event_manager: self,
+            once: false,
+            priority: None,
+            phase: None,
+            tag: None,
+            filter: None,
+            event: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    struct DamageDealt(u32);
+
+    #[test]
+    fn once_priority_and_filter_combine() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+        let listener = |name: &'static str| {
+            let tx = tx.clone();
+            move |event: &DamageDealt| {
+                let _ = tx.send((name, event.0));
+            }
+        };
+
+        event_manager.subscribe(listener("first"));
+        event_manager.subscribe_with_priority(5, listener("armor"));
+        let critical = event_manager
+            .subscription::<DamageDealt>()
+            .once()
+            .with_priority(10)
+            .filter(|event| event.0 >= 50)
+            .subscribe(listener("critical"));
+        event_manager.subscribe(listener("last"));
+
+        // Filtered out, so the once-listener stays subscribed.
+        event_manager.dispatch(&DamageDealt(10));
+        assert_eq!(event_manager.listener_count::<DamageDealt>(), 4);
+        event_manager.dispatch(&DamageDealt(60));
+        event_manager.dispatch(&DamageDealt(70));
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            vec![
+                ("armor", 10),
+                ("first", 10),
+                ("last", 10),
+                ("critical", 60),
+                ("armor", 60),
+                ("first", 60),
+                ("last", 60),
+                ("armor", 70),
+                ("first", 70),
+                ("last", 70),
+            ]
+        );
+        assert!(!event_manager.listener_ids::<DamageDealt>().contains(&critical));
+
+        // Removing it left the rest in order, so new listeners still slot in by priority.
+        event_manager.subscription::<DamageDealt>().with_priority(5).subscribe(listener("shield"));
+        event_manager.dispatch(&DamageDealt(1));
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            vec![("armor", 1), ("shield", 1), ("first", 1), ("last", 1)]
+        );
+    }
+
+    #[test]
+    fn fired_once_listeners_drop_their_options() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel();
+        let once = event_manager
+            .subscription::<DamageDealt>()
+            .once()
+            .with_priority(5)
+            .in_phase(Phase::PostUpdate)
+            .tagged("combat")
+            .subscribe(move |event: &DamageDealt| {
+                let _ = tx.send(event.0);
+            });
+        event_manager.dispatch(&DamageDealt(3));
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![3]);
+
+        let type_id = TypeId::of::<DamageDealt>();
+        assert!(!event_manager.priorities[&type_id].contains_key(&once));
+        assert!(!event_manager.phases[&type_id].contains_key(&once));
+        assert!(!event_manager.tags[&type_id].contains_key(&once));
+
+        let mut host = EventManager::new();
+        assert!(host.merge(event_manager).is_empty());
+    }
+}
@@ -0,0 +1,121 @@
+use std::any::{Any, TypeId};
+use std::collections::HashSet;
+
+use crate::{EventManager, Step, SubscriptionId};
+
+impl EventManager {
+    /// Subscribes a listener to `E` in the tag group `tag`. Within a phase and priority, tag
+    /// groups run in the order given to `set_tag_order`, followed by untagged listeners and
+    /// those whose tag isn't listed. Listeners sharing a tag keep their subscription order.
+    pub fn subscribe_tagged<E: Any + 'static>(&mut self, tag: &str, listener: impl FnMut(&E) + 'static) -> SubscriptionId {
+        let type_id = TypeId::of::<E>();
+        let id = self.subscribe(listener);
+        self.tags.entry(type_id).or_default().insert(id, tag.to_string());
+        // The new listener has no ordering constraints, so this can't introduce a cycle.
+        let _ = self.reorder(type_id);
+        id
+    }
+
+    /// Sets the order tag groups run in for every event type, e.g. from app config.
+    /// Repeated tags keep their first position.
+    pub fn set_tag_order(&mut self, order: Vec<String>) {
+        self.tag_order = order;
+        let tagged: Vec<TypeId> = self.tags.keys().copied().collect();
+        for type_id in tagged {
+            // Only the base order changed; the constraints are as acyclic as before.
+            let _ = self.reorder(type_id);
+        }
+    }
+
+    /// Dispatches `event` only to the listeners of `E` in the tag group `group`, in their
+    /// usual order, skipping every other listener. Returns how many listeners were invoked.
+    pub fn dispatch_to_group<E: Any + 'static>(&mut self, group: &str, event: &E) -> usize {
+        let type_id = TypeId::of::<E>();
+        let members: HashSet<SubscriptionId> = self
+            .tags
+            .get(&type_id)
+            .map(|tags| tags.iter().filter(|(_, tag)| *tag == group).map(|(id, _)| *id).collect())
+            .unwrap_or_default();
+        if members.is_empty() {
+            return 0;
+        }
+
+        let invoked = self.dispatch_selected(
+            type_id,
+            std::any::type_name::<E>(),
+            event,
+            Some(&mut |_, id| if members.contains(&id) { Step::Run } else { Step::Skip }),
+        );
+        self.flush_chained();
+        invoked
+    }
+}
+
+// Sort key placing a listener with `tag` according to `order`; unlisted or missing tags go last.
+pub(crate) fn tag_rank(order: &[String], tag: Option<&String>) -> usize {
+    tag.and_then(|tag| order.iter().position(|listed| listed == tag)).unwrap_or(order.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    struct SpellCast;
+
+    #[test]
+    fn tag_groups_follow_the_configured_order() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<&'static str>();
+
+        for (tag, name) in [("effects", "particles"), ("logging", "log"), ("validation", "check mana"), ("effects", "sound")] {
+            let tx = tx.clone();
+            event_manager.subscribe_tagged(tag, move |_: &SpellCast| {
+                let _ = tx.send(name);
+            });
+        }
+        let tx_untagged = tx.clone();
+        event_manager.subscribe(move |_: &SpellCast| {
+            let _ = tx_untagged.send("untagged");
+        });
+
+        // Without a tag order every group counts as unlisted, so subscription order holds.
+        event_manager.dispatch(&SpellCast);
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            vec!["particles", "log", "check mana", "sound", "untagged"]
+        );
+
+        event_manager.set_tag_order(vec!["validation".to_string(), "effects".to_string()]);
+        event_manager.dispatch(&SpellCast);
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            vec!["check mana", "particles", "sound", "log", "untagged"]
+        );
+
+        event_manager.set_tag_order(vec!["logging".to_string(), "validation".to_string(), "effects".to_string()]);
+        event_manager.dispatch(&SpellCast);
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            vec!["log", "check mana", "particles", "sound", "untagged"]
+        );
+    }
+
+    #[test]
+    fn dispatch_to_group_reaches_only_that_group() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<&'static str>();
+
+        for (tag, name) in [("debug", "overlay"), ("gameplay", "score"), ("debug", "console")] {
+            let tx = tx.clone();
+            event_manager.subscribe_tagged(tag, move |_: &SpellCast| {
+                let _ = tx.send(name);
+            });
+        }
+        event_manager.subscribe(|_: &SpellCast| panic!("untagged listeners aren't in any group"));
+
+        assert_eq!(event_manager.dispatch_to_group("debug", &SpellCast), 2);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["overlay", "console"]);
+        assert_eq!(event_manager.dispatch_to_group("audio", &SpellCast), 0);
+    }
+}
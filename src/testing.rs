@@ -0,0 +1,173 @@
+//! Helpers for testing code that dispatches events. Enabled by the `testing` feature.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::{EventManager, SubscriptionId};
+
+/// Captures every `E` dispatched on the managers it is attached to.
+pub struct EventSpy<E> {
+    received: Rc<RefCell<Vec<E>>>,
+}
+
+impl<E: Any + Clone + 'static> Default for EventSpy<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Any + Clone + 'static> EventSpy<E> {
+    pub fn new() -> Self {
+        EventSpy {
+            received: Rc::new(RefCell::new(Vec::new())),
+        }
+    }
+
+    /// Subscribes the spy to `E` on `manager`. A spy can be attached to several managers.
+    pub fn attach(&self, manager: &mut EventManager) -> SubscriptionId {
+        let received = Rc::clone(&self.received);
+        manager.subscribe(move |event: &E| received.borrow_mut().push(event.clone()))
+    }
+
+    /// Returns a copy of every event captured so far, in dispatch order.
+    pub fn received(&self) -> Vec<E> {
+        self.received.borrow().clone()
+    }
+
+    /// Returns how many events have been captured so far.
+    pub fn count(&self) -> usize {
+        self.received.borrow().len()
+    }
+
+    /// Panics unless exactly `expected` events have been captured.
+    #[track_caller]
+    pub fn assert_count(&self, expected: usize) {
+        let actual = self.count();
+        assert_eq!(
+            actual,
+            expected,
+            "expected {expected} `{}` event(s), received {actual}",
+            std::any::type_name::<E>()
+        );
+    }
+
+    /// Forgets every event captured so far.
+    pub fn clear(&self) {
+        self.received.borrow_mut().clear();
+    }
+}
+
+/// Records the order listeners fire in, for asserting on phases, tags and `subscribe_after`
+/// constraints without wiring up channels. Clones share the same record.
+#[derive(Clone, Default)]
+pub struct OrderRecorder {
+    order: Rc<RefCell<Vec<String>>>,
+}
+
+impl OrderRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a listener for any `E` that records `name` each time it runs. Pass it to
+    /// whichever `subscribe` variant is under test.
+    pub fn listener<E>(&self, name: &str) -> impl FnMut(&E) + 'static {
+        let recorder = self.clone();
+        let name = name.to_string();
+        move |_: &E| recorder.record(&name)
+    }
+
+    /// Records `name` directly, e.g. from inside a hand-written listener.
+    pub fn record(&self, name: &str) {
+        self.order.borrow_mut().push(name.to_string());
+    }
+
+    /// Returns every recorded name, in firing order.
+    pub fn order(&self) -> Vec<String> {
+        self.order.borrow().clone()
+    }
+
+    /// Panics unless `first` was recorded, and recorded before every record of `then`.
+    #[track_caller]
+    pub fn assert_before(&self, first: &str, then: &str) {
+        let order = self.order.borrow();
+        let first_at = order.iter().position(|name| name == first);
+        let then_at = order.iter().position(|name| name == then);
+        match (first_at, then_at) {
+            (None, _) => panic!("`{first}` never fired; recorded order: {order:?}"),
+            (Some(first_at), Some(then_at)) if then_at < first_at => {
+                panic!("expected `{first}` before `{then}`; recorded order: {order:?}")
+            }
+            _ => {}
+        }
+    }
+
+    /// Forgets everything recorded so far.
+    pub fn clear(&self) {
+        self.order.borrow_mut().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct PlayerJumped {
+        player_id: u32,
+    }
+
+    #[test]
+    fn spy_captures_dispatched_events() {
+        let mut event_manager = EventManager::new();
+        let spy = EventSpy::<PlayerJumped>::new();
+        spy.attach(&mut event_manager);
+
+        spy.assert_count(0);
+        event_manager.dispatch(&PlayerJumped { player_id: 1 });
+        event_manager.dispatch(&PlayerJumped { player_id: 2 });
+        event_manager.dispatch(&42u32);
+
+        spy.assert_count(2);
+        assert_eq!(spy.received(), vec![PlayerJumped { player_id: 1 }, PlayerJumped { player_id: 2 }]);
+
+        spy.clear();
+        spy.assert_count(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 1")]
+    fn assert_count_panics_on_mismatch() {
+        let spy = EventSpy::<PlayerJumped>::new();
+        spy.assert_count(1);
+    }
+
+    #[test]
+    fn order_recorder_checks_relative_order() {
+        let mut event_manager = EventManager::new();
+        let recorder = OrderRecorder::new();
+
+        event_manager.subscribe_in_phase(crate::Phase::PostUpdate, recorder.listener::<PlayerJumped>("render"));
+        event_manager.subscribe(recorder.listener::<PlayerJumped>("physics"));
+        event_manager.subscribe_in_phase(crate::Phase::PreUpdate, recorder.listener::<PlayerJumped>("input"));
+        event_manager.dispatch(&PlayerJumped { player_id: 1 });
+
+        assert_eq!(recorder.order(), vec!["input", "physics", "render"]);
+        recorder.assert_before("input", "physics");
+        recorder.assert_before("physics", "render");
+        recorder.assert_before("render", "never fired");
+
+        recorder.clear();
+        assert!(recorder.order().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "expected `render` before `input`")]
+    fn assert_before_panics_on_the_wrong_order() {
+        let recorder = OrderRecorder::new();
+        recorder.record("input");
+        recorder.record("render");
+        recorder.assert_before("render", "input");
+    }
+}
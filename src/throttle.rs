@@ -0,0 +1,143 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::{EventManager, SubscriptionId};
+
+/// What a throttled listener does with events that arrive before its interval has elapsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Throttle {
+    /// Ignore them.
+    Drop,
+    /// Keep the most recent one and deliver it from `flush_throttled` once the interval
+    /// has elapsed, unless a newer event gets through first.
+    Latest,
+}
+
+struct ThrottleState<E, F> {
+    listener: F,
+    min_interval: Duration,
+    mode: Throttle,
+    last_fired: Option<Instant>,
+    pending: Option<E>,
+}
+
+impl<E, F: FnMut(&E)> ThrottleState<E, F> {
+    fn ready(&self, now: Instant) -> bool {
+        self.last_fired.is_none_or(|last| now.duration_since(last) >= self.min_interval)
+    }
+
+    fn fire(&mut self, event: &E, now: Instant) {
+        self.last_fired = Some(now);
+        self.pending = None;
+        (self.listener)(event);
+    }
+}
+
+impl EventManager {
+    /// Subscribes a listener to `E` that runs at most once per `min_interval`.
+    /// Intervals are measured with `Instant::now()` at dispatch; `mode` decides whether
+    /// events inside the interval are dropped or coalesced into the latest one, which
+    /// `flush_throttled` then delivers.
+    pub fn subscribe_throttled<E: Any + Clone + 'static>(
+        &mut self,
+        min_interval: Duration,
+        mode: Throttle,
+        listener: impl FnMut(&E) + 'static,
+    ) -> SubscriptionId {
+        let state = Rc::new(RefCell::new(ThrottleState {
+            listener,
+            min_interval,
+            mode,
+            last_fired: None,
+            pending: None,
+        }));
+
+        // The flusher only holds a weak reference, so it goes away with the listener.
+        let weak = Rc::downgrade(&state);
+        self.throttled.push(Box::new(move |now| {
+            let Some(state) = weak.upgrade() else {
+                return false;
+            };
+            let mut state = state.borrow_mut();
+            if state.ready(now) {
+                if let Some(event) = state.pending.take() {
+                    state.fire(&event, now);
+                }
+            }
+            true
+        }));
+
+        self.subscribe(move |event: &E| {
+            let now = Instant::now();
+            let mut state = state.borrow_mut();
+            if state.ready(now) {
+                state.fire(event, now);
+            } else if state.mode == Throttle::Latest {
+                state.pending = Some(event.clone());
+            }
+        })
+    }
+
+    /// Delivers the coalesced events of `Throttle::Latest` listeners whose interval has
+    /// elapsed. Call it regularly (e.g. once per frame) so the last event of a burst isn't
+    /// held back until the next dispatch.
+    pub fn flush_throttled(&mut self) {
+        let now = Instant::now();
+        self.throttled.retain_mut(|flush| flush(now));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::thread;
+
+    #[derive(Clone)]
+    struct DocumentChanged(u32);
+
+    // Orders of magnitude longer than the back-to-back dispatches the tests expect to land
+    // inside one interval, so a stalled test thread can't push them apart. Leaving the
+    // interval relies on `thread::sleep`, which never returns early.
+    const INTERVAL: Duration = Duration::from_millis(500);
+
+    #[test]
+    fn drop_mode_ignores_events_inside_the_interval() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<u32>();
+        event_manager.subscribe_throttled(INTERVAL, Throttle::Drop, move |event: &DocumentChanged| {
+            let _ = tx.send(event.0);
+        });
+
+        event_manager.dispatch(&DocumentChanged(1));
+        event_manager.dispatch(&DocumentChanged(2));
+        event_manager.dispatch(&DocumentChanged(3));
+        thread::sleep(INTERVAL);
+        event_manager.flush_throttled();
+        event_manager.dispatch(&DocumentChanged(4));
+
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![1, 4]);
+    }
+
+    #[test]
+    fn latest_mode_delivers_the_last_event_on_flush() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<u32>();
+        event_manager.subscribe_throttled(INTERVAL, Throttle::Latest, move |event: &DocumentChanged| {
+            let _ = tx.send(event.0);
+        });
+
+        event_manager.dispatch(&DocumentChanged(1));
+        event_manager.dispatch(&DocumentChanged(2));
+        event_manager.dispatch(&DocumentChanged(3));
+        event_manager.flush_throttled();
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![1], "Still inside the interval");
+
+        thread::sleep(INTERVAL);
+        event_manager.flush_throttled();
+        event_manager.flush_throttled();
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![3]);
+    }
+}
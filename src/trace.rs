@@ -0,0 +1,183 @@
+use std::any::{Any, TypeId};
+use std::time::{Duration, Instant};
+
+use crate::{EventManager, Phase, Step, SubscriptionId};
+
+/// A record of one `dispatch_traced` call.
+#[derive(Debug, Clone)]
+pub struct DispatchTrace {
+    /// The type name of the dispatched event.
+    pub event: &'static str,
+    /// Every listener that ran, in the order it ran.
+    pub listeners: Vec<ListenerTrace>,
+}
+
+/// One listener's part in a `DispatchTrace`.
+#[derive(Debug, Clone)]
+pub struct ListenerTrace {
+    pub id: SubscriptionId,
+    /// The name given to `subscribe_named`, if any.
+    pub name: Option<&'static str>,
+    /// The listener's phase; `None` for `subscribe_fn` listeners, which run after all phases.
+    pub phase: Option<Phase>,
+    pub elapsed: Duration,
+}
+
+impl DispatchTrace {
+    /// Groups the trace by phase, in the order the phases ran: each phase with how many of
+    /// its listeners ran and their total time. `subscribe_fn` listeners come last, as `None`.
+    pub fn phase_totals(&self) -> Vec<(Option<Phase>, usize, Duration)> {
+        let mut totals: Vec<(Option<Phase>, usize, Duration)> = Vec::new();
+        for listener in &self.listeners {
+            match totals.last_mut() {
+                Some((phase, count, elapsed)) if *phase == listener.phase => {
+                    *count += 1;
+                    *elapsed += listener.elapsed;
+                }
+                _ => totals.push((listener.phase, 1, listener.elapsed)),
+            }
+        }
+        totals
+    }
+}
+
+impl EventManager {
+    /// Subscribes a listener to `E` like `subscribe`, tagged with a human-readable `name`
+    /// that shows up in `listener_names` and `dispatch_traced`.
+    pub fn subscribe_named<E: Any + 'static>(&mut self, name: &'static str, listener: impl FnMut(&E) + 'static) -> SubscriptionId {
+        let id = self.subscribe(listener);
+        self.names.entry(TypeId::of::<E>()).or_default().insert(id, name);
+        id
+    }
+
+    /// Returns the names of the named listeners of `E`, in dispatch order.
+    /// Listeners subscribed without a name are left out.
+    pub fn listener_names<E: Any + 'static>(&self) -> Vec<&'static str> {
+        let type_id = TypeId::of::<E>();
+        let (Some(bucket), Some(names)) = (self.listeners.get(&type_id), self.names.get(&type_id)) else {
+            return Vec::new();
+        };
+        bucket.live_ids().filter_map(|id| names.get(&id).copied()).collect()
+    }
+
+    /// Returns how many listeners of `E` are subscribed in `phase`.
+    pub fn phase_listener_count<E: Any + 'static>(&self, phase: Phase) -> usize {
+        let type_id = TypeId::of::<E>();
+        let Some(bucket) = self.listeners.get(&type_id) else {
+            return 0;
+        };
+        let phases = self.phases.get(&type_id);
+        bucket
+            .live_ids()
+            .filter(|id| phases.and_then(|phases| phases.get(id)).copied().unwrap_or(Phase::Update) == phase)
+            .count()
+    }
+
+    /// Dispatches `event` like `dispatch` and returns how long each listener took, in the
+    /// order they ran. A shorthand for `dispatch_traced` when names and phases don't matter;
+    /// neither adds any cost to a plain `dispatch`.
+    pub fn dispatch_timed<E: Any + 'static>(&mut self, event: &E) -> Vec<(SubscriptionId, Duration)> {
+        self.dispatch_traced(event).listeners.into_iter().map(|listener| (listener.id, listener.elapsed)).collect()
+    }
+
+    /// Dispatches `event` like `dispatch` and reports which listeners ran, with their names,
+    /// phases and how long each took. Follow-ups from chaining listeners are dispatched but not traced.
+    pub fn dispatch_traced<E: Any + 'static>(&mut self, event: &E) -> DispatchTrace {
+        let type_id = TypeId::of::<E>();
+        // Positions past the mutable listeners belong to `subscribe_fn` listeners.
+        let mutable = self.listeners.get(&type_id).map_or(0, |bucket| bucket.live_count());
+        let mut started: Vec<(SubscriptionId, bool, Instant)> = Vec::new();
+        self.dispatch_selected(
+            type_id,
+            std::any::type_name::<E>(),
+            event,
+            Some(&mut |position, id| {
+                started.push((id, position < mutable, Instant::now()));
+                Step::Run
+            }),
+        );
+        let finished = Instant::now();
+        self.flush_chained();
+
+        // Each listener ran until the next one was about to start.
+        let names = self.names.get(&type_id);
+        let phases = self.phases.get(&type_id);
+        let ends = started.iter().skip(1).map(|(_, _, at)| *at).chain([finished]);
+        let listeners = started
+            .iter()
+            .zip(ends)
+            .map(|(&(id, is_mutable, start), end)| ListenerTrace {
+                id,
+                name: names.and_then(|names| names.get(&id)).copied(),
+                phase: is_mutable.then(|| phases.and_then(|phases| phases.get(&id)).copied().unwrap_or(Phase::Update)),
+                elapsed: end.duration_since(start),
+            })
+            .collect();
+        DispatchTrace { event: std::any::type_name::<E>(), listeners }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct SaveRequested;
+
+    #[test]
+    fn names_appear_in_listener_names_and_traces() {
+        let mut event_manager = EventManager::new();
+        let serialize = event_manager.subscribe_named("serialize world", |_: &SaveRequested| {});
+        let anonymous = event_manager.subscribe(|_: &SaveRequested| {});
+        event_manager.subscribe_named("write to disk", |_: &SaveRequested| {});
+
+        assert_eq!(event_manager.listener_names::<SaveRequested>(), vec!["serialize world", "write to disk"]);
+        assert!(event_manager.listener_names::<u32>().is_empty());
+
+        let trace = event_manager.dispatch_traced(&SaveRequested);
+        assert!(trace.event.ends_with("SaveRequested"));
+        let names: Vec<_> = trace.listeners.iter().map(|listener| listener.name).collect();
+        assert_eq!(names, vec![Some("serialize world"), None, Some("write to disk")]);
+        assert_eq!(trace.listeners[0].id, serialize);
+        assert_eq!(trace.listeners[1].id, anonymous);
+    }
+
+    #[test]
+    fn timed_dispatch_reports_every_listener() {
+        let mut event_manager = EventManager::new();
+        event_manager.subscribe(|_: &SaveRequested| std::thread::sleep(Duration::from_millis(2)));
+        event_manager.subscribe_named("flush", |_: &SaveRequested| {});
+        event_manager.subscribe_fn(|_: &SaveRequested| {});
+
+        let timings = event_manager.dispatch_timed(&SaveRequested);
+        let ids: Vec<_> = timings.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, event_manager.listener_ids::<SaveRequested>());
+        assert!(timings[0].1 >= Duration::from_millis(2));
+    }
+
+    #[test]
+    fn traces_break_down_by_phase() {
+        let mut event_manager = EventManager::new();
+        event_manager.subscribe_in_phase(Phase::PostUpdate, |_: &SaveRequested| {});
+        event_manager.subscribe_in_phase(Phase::PreUpdate, |_: &SaveRequested| {});
+        event_manager.subscribe(|_: &SaveRequested| {});
+        event_manager.subscribe_in_phase(Phase::PreUpdate, |_: &SaveRequested| {});
+        event_manager.subscribe_fn(|_: &SaveRequested| {});
+
+        assert_eq!(event_manager.phase_listener_count::<SaveRequested>(Phase::PreUpdate), 2);
+        assert_eq!(event_manager.phase_listener_count::<SaveRequested>(Phase::Update), 1);
+        assert_eq!(event_manager.phase_listener_count::<SaveRequested>(Phase::PostUpdate), 1);
+        assert_eq!(event_manager.phase_listener_count::<SaveRequested>(Phase::Custom(0)), 0);
+
+        let trace = event_manager.dispatch_traced(&SaveRequested);
+        let breakdown: Vec<_> = trace.phase_totals().into_iter().map(|(phase, count, _)| (phase, count)).collect();
+        assert_eq!(
+            breakdown,
+            vec![
+                (Some(Phase::PreUpdate), 2),
+                (Some(Phase::Update), 1),
+                (Some(Phase::PostUpdate), 1),
+                (None, 1),
+            ]
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{ListenerEntry, SubscriptionId};
+
+// Listeners of a `TraitEventManager<T>`, taking the trait object directly.
+type TraitListener<T> = Box<dyn FnMut(&T)>;
+
+/// An event manager for events that are trait objects rather than concrete types.
+///
+/// Where `EventManager` routes each event by its `TypeId`, a `TraitEventManager<dyn T>` has a
+/// single listener list: every dispatched `&dyn T` reaches every listener, which inspects the
+/// event through the trait's own methods. Use it when events are naturally a family of types
+/// behind one trait, such as commands or network messages.
+pub struct TraitEventManager<T: ?Sized> {
+    listeners: Vec<ListenerEntry<TraitListener<T>>>,
+    next_id: u64,
+}
+
+impl<T: ?Sized> Default for TraitEventManager<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ?Sized> TraitEventManager<T> {
+    pub fn new() -> Self {
+        TraitEventManager {
+            listeners: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Subscribes a listener that receives every dispatched event.
+    pub fn subscribe(&mut self, listener: impl FnMut(&T) + 'static) -> SubscriptionId {
+        let id = SubscriptionId(self.next_id);
+        self.next_id += 1;
+        self.listeners.push(ListenerEntry { id, listener: Box::new(listener), retired: None });
+        id
+    }
+
+    /// Dispatches `event` to all listeners in subscription order.
+    pub fn dispatch(&mut self, event: &T) {
+        for entry in &mut self.listeners {
+            (entry.listener)(event);
+        }
+    }
+
+    /// Returns how many listeners are subscribed.
+    pub fn listener_count(&self) -> usize {
+        self.listeners.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    trait Packet {
+        fn channel(&self) -> u8;
+    }
+
+    struct Chat;
+    struct Movement;
+
+    impl Packet for Chat {
+        fn channel(&self) -> u8 {
+            1
+        }
+    }
+
+    impl Packet for Movement {
+        fn channel(&self) -> u8 {
+            2
+        }
+    }
+
+    #[test]
+    fn every_trait_object_reaches_every_listener() {
+        let mut event_manager: TraitEventManager<dyn Packet> = TraitEventManager::new();
+        let (tx, rx) = mpsc::channel::<u8>();
+
+        event_manager.subscribe(move |packet| {
+            let _ = tx.send(packet.channel());
+        });
+        let packets: Vec<Box<dyn Packet>> = vec![Box::new(Movement), Box::new(Chat)];
+        for packet in &packets {
+            event_manager.dispatch(packet.as_ref());
+        }
+
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![2, 1]);
+        assert_eq!(event_manager.listener_count(), 1);
+    }
+}
@@ -0,0 +1,85 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::{EventManager, SubscriptionId};
+
+struct Window<E, L> {
+    buffer: Vec<E>,
+    listener: L,
+}
+
+impl EventManager {
+    /// Subscribes a listener to `E` that receives events in batches: each event is buffered,
+    /// and once `window` of them have arrived the listener gets them all as one slice, oldest
+    /// first, and the buffer starts over. `flush_windows` delivers partial batches early.
+    /// A `window` of 0 is treated as 1.
+    pub fn subscribe_windowed<E: Any + Clone + 'static>(
+        &mut self,
+        window: usize,
+        listener: impl FnMut(&[E]) + 'static,
+    ) -> SubscriptionId {
+        let window = window.max(1);
+        let state = Rc::new(RefCell::new(Window { buffer: Vec::with_capacity(window), listener }));
+
+        // Like reducers, the flusher goes away with the listener.
+        let weak = Rc::downgrade(&state);
+        self.windows.push(Box::new(move || {
+            let Some(state) = weak.upgrade() else {
+                return false;
+            };
+            let mut state = state.borrow_mut();
+            let Window { buffer, listener } = &mut *state;
+            if !buffer.is_empty() {
+                listener(buffer);
+                buffer.clear();
+            }
+            true
+        }));
+
+        self.subscribe(move |event: &E| {
+            let mut state = state.borrow_mut();
+            let Window { buffer, listener } = &mut *state;
+            buffer.push(event.clone());
+            if buffer.len() == window {
+                listener(buffer);
+                buffer.clear();
+            }
+        })
+    }
+
+    /// Hands every windowed listener's partial batch to it right away, in subscription
+    /// order, e.g. at the end of a frame. Listeners with an empty buffer aren't called.
+    pub fn flush_windows(&mut self) {
+        self.windows.retain_mut(|flush| flush());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[derive(Clone)]
+    struct LogLine(u32);
+
+    #[test]
+    fn events_arrive_in_full_windows_then_flush() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<Vec<u32>>();
+
+        event_manager.subscribe_windowed(3, move |batch: &[LogLine]| {
+            let _ = tx.send(batch.iter().map(|line| line.0).collect());
+        });
+
+        for line in 1..=7 {
+            event_manager.dispatch(&LogLine(line));
+        }
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![vec![1, 2, 3], vec![4, 5, 6]]);
+
+        event_manager.flush_windows();
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![vec![7]]);
+        event_manager.flush_windows();
+        assert!(rx.try_iter().next().is_none(), "an empty window isn't delivered");
+    }
+}
@@ -0,0 +1,139 @@
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::{EventManager, Listener, SubscriptionId};
+
+// A named handler; shared so one handler can be wired to several event types.
+type NamedHandler = Rc<RefCell<Listener>>;
+
+// Event types and handlers registered by name for `wire_from_config`.
+#[derive(Default)]
+pub(crate) struct WiringRegistry {
+    event_types: HashMap<String, TypeId>,
+    handlers: HashMap<String, NamedHandler>,
+}
+
+/// Errors from `EventManager::wire_from_config`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WiringError {
+    /// No event type was registered under this name.
+    UnknownEvent(String),
+    /// No handler was registered under this name.
+    UnknownHandler(String),
+}
+
+impl fmt::Display for WiringError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WiringError::UnknownEvent(name) => write!(f, "no event type is registered as {name:?}"),
+            WiringError::UnknownHandler(name) => write!(f, "no handler is registered as {name:?}"),
+        }
+    }
+}
+
+impl std::error::Error for WiringError {}
+
+impl EventManager {
+    /// Registers `E` under `name` so config can refer to it. Replaces any type registered
+    /// under the same name.
+    pub fn register_event_type<E: Any + 'static>(&mut self, name: &str) {
+        self.remember_type::<E>();
+        self.wiring.event_types.insert(name.to_string(), TypeId::of::<E>());
+    }
+
+    /// Registers `handler` under `name` so config can wire it to event types. A handler wired
+    /// to several types receives each of them and downcasts the event itself. Replaces any
+    /// handler registered under the same name, but not the subscriptions already wired to it.
+    pub fn register_handler(&mut self, name: &str, handler: Listener) {
+        self.wiring.handlers.insert(name.to_string(), Rc::new(RefCell::new(handler)));
+    }
+
+    /// Subscribes the named handler of every `(event_name, handler_name)` pair to the named
+    /// event type, in order, returning the new subscriptions. If any name is unknown, nothing
+    /// is subscribed and the first such name is reported.
+    pub fn wire_from_config(&mut self, wiring: &[(&str, &str)]) -> Result<Vec<SubscriptionId>, WiringError> {
+        let mut resolved = Vec::with_capacity(wiring.len());
+        for (event_name, handler_name) in wiring {
+            let Some(&type_id) = self.wiring.event_types.get(*event_name) else {
+                return Err(WiringError::UnknownEvent(event_name.to_string()));
+            };
+            let Some(handler) = self.wiring.handlers.get(*handler_name) else {
+                return Err(WiringError::UnknownHandler(handler_name.to_string()));
+            };
+            resolved.push((type_id, Rc::clone(handler)));
+        }
+
+        let ids = resolved
+            .into_iter()
+            .map(|(type_id, handler)| {
+                // Listeners can't dispatch, so a handler is never borrowed twice at once.
+                self.push_listener(type_id, Box::new(move |event: &dyn Any| (handler.borrow_mut())(event)), None)
+            })
+            .collect();
+        Ok(ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    struct DoorOpened;
+    struct AlarmTripped(u8);
+
+    #[test]
+    fn config_pairs_wire_handlers_to_events() {
+        let mut event_manager = EventManager::new();
+        let (tx, rx) = mpsc::channel::<String>();
+
+        event_manager.register_event_type::<DoorOpened>("door_opened");
+        event_manager.register_event_type::<AlarmTripped>("alarm_tripped");
+        let tx_sound = tx.clone();
+        event_manager.register_handler(
+            "play_sound",
+            Box::new(move |event| {
+                let sound = if event.is::<DoorOpened>() { "creak" } else { "siren" };
+                let _ = tx_sound.send(sound.to_string());
+            }),
+        );
+        let tx_guard = tx.clone();
+        event_manager.register_handler(
+            "alert_guard",
+            Box::new(move |event| {
+                if let Some(alarm) = event.downcast_ref::<AlarmTripped>() {
+                    let _ = tx_guard.send(format!("guard to zone {}", alarm.0));
+                }
+            }),
+        );
+
+        let ids = event_manager
+            .wire_from_config(&[("door_opened", "play_sound"), ("alarm_tripped", "play_sound"), ("alarm_tripped", "alert_guard")])
+            .unwrap();
+        assert_eq!(ids.len(), 3);
+
+        event_manager.dispatch(&DoorOpened);
+        event_manager.dispatch(&AlarmTripped(4));
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec!["creak", "siren", "guard to zone 4"]);
+    }
+
+    #[test]
+    fn unknown_names_wire_nothing() {
+        let mut event_manager = EventManager::new();
+        event_manager.register_event_type::<DoorOpened>("door_opened");
+        event_manager.register_handler("log", Box::new(|_| {}));
+
+        assert_eq!(
+            event_manager.wire_from_config(&[("door_opened", "log"), ("door_closed", "log")]),
+            Err(WiringError::UnknownEvent("door_closed".to_string()))
+        );
+        assert_eq!(
+            event_manager.wire_from_config(&[("door_opened", "beep")]),
+            Err(WiringError::UnknownHandler("beep".to_string()))
+        );
+        assert!(event_manager.is_empty());
+    }
+}